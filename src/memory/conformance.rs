@@ -0,0 +1,344 @@
+//! Backend-agnostic conformance suite for any [`Memory`] implementation.
+//!
+//! [`OracleMemory`](crate::oracle::OracleMemory) and any future backend only
+//! get exercised end-to-end by hand today; there is no check that a backend
+//! actually satisfies the contract `store`/`recall`/`get`/`list`/`forget`/
+//! `count` are supposed to promise. [`run_memory_conformance`] runs a fixed,
+//! ordered table of [`ConformanceCase`]s -- each an operation plus the
+//! assertion it's expected to satisfy, in the spirit of a record-driven SQL
+//! test runner -- against a `&dyn Memory` and aggregates the failures into a
+//! [`ConformanceReport`] instead of panicking on the first one.
+//!
+//! Run it against `InMemoryTestBackend` in a plain unit test (see the tests
+//! below) to exercise the harness itself, and against a real
+//! `OracleMemory` from an integration test gated behind
+//! `ZEROORACLAW_ORACLE_INTEGRATION_TESTS=1`, since that path needs a live
+//! database.
+
+use crate::memory::traits::{Memory, MemoryCategory, MemoryEntry};
+
+/// One operation [`run_memory_conformance`] can issue against a [`Memory`].
+#[derive(Debug, Clone)]
+pub enum Op {
+    Store {
+        key: &'static str,
+        content: &'static str,
+        category: MemoryCategory,
+        session_id: Option<&'static str>,
+    },
+    Get {
+        key: &'static str,
+    },
+    Recall {
+        query: &'static str,
+        limit: usize,
+        session_id: Option<&'static str>,
+    },
+    List {
+        category: Option<MemoryCategory>,
+        session_id: Option<&'static str>,
+    },
+    Forget {
+        key: &'static str,
+    },
+    Count,
+}
+
+/// The result of running one [`ConformanceCase`]'s [`Op`], handed to its
+/// `assert` function.
+#[derive(Debug)]
+pub enum Outcome {
+    Stored,
+    Got(Option<MemoryEntry>),
+    Recalled(Vec<MemoryEntry>),
+    Listed(Vec<MemoryEntry>),
+    Forgot(bool),
+    Counted(usize),
+}
+
+/// One declarative step of the conformance suite: an [`Op`] to run, and the
+/// assertion its [`Outcome`] must satisfy (`Err` carries a human-readable
+/// failure description).
+pub struct ConformanceCase {
+    pub name: &'static str,
+    pub op: Op,
+    pub assert: fn(&Outcome) -> Result<(), String>,
+}
+
+/// Aggregated result of a [`run_memory_conformance`] run.
+#[derive(Debug, Default)]
+pub struct ConformanceReport {
+    pub passed: Vec<&'static str>,
+    pub failed: Vec<(&'static str, String)>,
+}
+
+impl ConformanceReport {
+    /// Whether every case passed (an empty suite counts as passing).
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Run [`conformance_cases`] in order against `mem`, sharing its state
+/// across cases (later cases depend on earlier ones' writes -- e.g. the
+/// upsert check re-stores a key the round-trip check just created).
+pub async fn run_memory_conformance(mem: &dyn Memory) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+
+    for case in conformance_cases() {
+        let outcome = match run_op(mem, &case.op).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                report.failed.push((case.name, format!("operation errored: {e}")));
+                continue;
+            }
+        };
+
+        match (case.assert)(&outcome) {
+            Ok(()) => report.passed.push(case.name),
+            Err(e) => report.failed.push((case.name, e)),
+        }
+    }
+
+    report
+}
+
+async fn run_op(mem: &dyn Memory, op: &Op) -> anyhow::Result<Outcome> {
+    Ok(match op {
+        Op::Store { key, content, category, session_id } => {
+            mem.store(key, content, category.clone(), *session_id).await?;
+            Outcome::Stored
+        }
+        Op::Get { key } => Outcome::Got(mem.get(key).await?),
+        Op::Recall { query, limit, session_id } => {
+            Outcome::Recalled(mem.recall(query, *limit, *session_id).await?)
+        }
+        Op::List { category, session_id } => {
+            Outcome::Listed(mem.list(category.as_ref(), *session_id).await?)
+        }
+        Op::Forget { key } => Outcome::Forgot(mem.forget(key).await?),
+        Op::Count => Outcome::Counted(mem.count().await?),
+    })
+}
+
+// ── Assertions ──────────────────────────────────────────────────────────
+
+fn assert_stored(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Stored => Ok(()),
+        other => Err(format!("expected Stored, got {other:?}")),
+    }
+}
+
+fn assert_got_first_store(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Got(Some(entry)) if entry.content == "first store" => Ok(()),
+        other => Err(format!("expected Got(content = \"first store\"), got {other:?}")),
+    }
+}
+
+fn assert_got_updated_store(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Got(Some(entry)) if entry.content == "updated store" => Ok(()),
+        other => Err(format!("expected Got(content = \"updated store\"), got {other:?}")),
+    }
+}
+
+fn assert_counted_one(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Counted(1) => Ok(()),
+        other => Err(format!("expected Counted(1), got {other:?}")),
+    }
+}
+
+fn assert_counted_three(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Counted(3) => Ok(()),
+        other => Err(format!("expected Counted(3), got {other:?}")),
+    }
+}
+
+fn assert_counted_two(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Counted(2) => Ok(()),
+        other => Err(format!("expected Counted(2), got {other:?}")),
+    }
+}
+
+fn assert_recall_scoped_to_session_a(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Recalled(entries) => {
+            if entries.iter().any(|e| e.session_id.as_deref() != Some("session_a")) {
+                return Err(format!(
+                    "recall scoped to session_a returned an entry from another session: {entries:?}"
+                ));
+            }
+            if entries.iter().all(|e| e.key != "conformance_beta") {
+                return Err("recall scoped to session_a should have found conformance_beta".into());
+            }
+            Ok(())
+        }
+        other => Err(format!("expected Recalled, got {other:?}")),
+    }
+}
+
+fn assert_recall_scores_non_increasing(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Recalled(entries) => {
+            let scored: Vec<f64> = entries.iter().filter_map(|e| e.score).collect();
+            if scored.windows(2).any(|w| w[0] < w[1]) {
+                return Err(format!("recall results are not sorted by non-increasing score: {scored:?}"));
+            }
+            Ok(())
+        }
+        other => Err(format!("expected Recalled, got {other:?}")),
+    }
+}
+
+fn assert_list_only_conversation(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Listed(entries) => {
+            if entries.iter().any(|e| e.category != MemoryCategory::Conversation) {
+                return Err(format!(
+                    "list(Conversation) returned a non-Conversation entry: {entries:?}"
+                ));
+            }
+            if entries.len() != 2 {
+                return Err(format!("expected 2 Conversation entries, got {}", entries.len()));
+            }
+            Ok(())
+        }
+        other => Err(format!("expected Listed, got {other:?}")),
+    }
+}
+
+fn assert_forgot_true(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Forgot(true) => Ok(()),
+        other => Err(format!("expected Forgot(true), got {other:?}")),
+    }
+}
+
+fn assert_forgot_false(outcome: &Outcome) -> Result<(), String> {
+    match outcome {
+        Outcome::Forgot(false) => Ok(()),
+        other => Err(format!("expected Forgot(false) -- forget should be idempotent, got {other:?}")),
+    }
+}
+
+/// The fixed, ordered conformance table. Cases share state with each other
+/// (they all run against the same `mem`), so reordering or removing one can
+/// change what later ones see -- append new coverage at the end instead of
+/// interleaving it.
+fn conformance_cases() -> Vec<ConformanceCase> {
+    vec![
+        ConformanceCase {
+            name: "store_creates_entry",
+            op: Op::Store {
+                key: "conformance_alpha",
+                content: "first store",
+                category: MemoryCategory::Core,
+                session_id: None,
+            },
+            assert: assert_stored,
+        },
+        ConformanceCase {
+            name: "get_round_trips_stored_content",
+            op: Op::Get { key: "conformance_alpha" },
+            assert: assert_got_first_store,
+        },
+        ConformanceCase {
+            name: "count_after_first_store",
+            op: Op::Count,
+            assert: assert_counted_one,
+        },
+        ConformanceCase {
+            name: "store_same_key_upserts",
+            op: Op::Store {
+                key: "conformance_alpha",
+                content: "updated store",
+                category: MemoryCategory::Core,
+                session_id: None,
+            },
+            assert: assert_stored,
+        },
+        ConformanceCase {
+            name: "count_unchanged_after_upsert",
+            op: Op::Count,
+            assert: assert_counted_one,
+        },
+        ConformanceCase {
+            name: "get_reflects_upsert",
+            op: Op::Get { key: "conformance_alpha" },
+            assert: assert_got_updated_store,
+        },
+        ConformanceCase {
+            name: "store_session_a_entry",
+            op: Op::Store {
+                key: "conformance_beta",
+                content: "needle in session a",
+                category: MemoryCategory::Conversation,
+                session_id: Some("session_a"),
+            },
+            assert: assert_stored,
+        },
+        ConformanceCase {
+            name: "store_session_b_entry",
+            op: Op::Store {
+                key: "conformance_gamma",
+                content: "needle in session b",
+                category: MemoryCategory::Conversation,
+                session_id: Some("session_b"),
+            },
+            assert: assert_stored,
+        },
+        ConformanceCase {
+            name: "count_after_three_entries",
+            op: Op::Count,
+            assert: assert_counted_three,
+        },
+        ConformanceCase {
+            name: "recall_is_scoped_to_session",
+            op: Op::Recall { query: "needle", limit: 10, session_id: Some("session_a") },
+            assert: assert_recall_scoped_to_session_a,
+        },
+        ConformanceCase {
+            name: "recall_scores_are_monotonic",
+            op: Op::Recall { query: "needle", limit: 10, session_id: None },
+            assert: assert_recall_scores_non_increasing,
+        },
+        ConformanceCase {
+            name: "list_filters_by_category",
+            op: Op::List { category: Some(MemoryCategory::Conversation), session_id: None },
+            assert: assert_list_only_conversation,
+        },
+        ConformanceCase {
+            name: "forget_existing_key_returns_true",
+            op: Op::Forget { key: "conformance_alpha" },
+            assert: assert_forgot_true,
+        },
+        ConformanceCase {
+            name: "forget_is_idempotent",
+            op: Op::Forget { key: "conformance_alpha" },
+            assert: assert_forgot_false,
+        },
+        ConformanceCase {
+            name: "count_reflects_forget",
+            op: Op::Count,
+            assert: assert_counted_two,
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::traits::InMemoryTestBackend;
+
+    #[tokio::test]
+    async fn conformance_suite_passes_against_in_memory_backend() {
+        let mem = InMemoryTestBackend::new();
+        let report = run_memory_conformance(&mem).await;
+        assert!(report.is_ok(), "conformance failures: {:?}", report.failed);
+    }
+}
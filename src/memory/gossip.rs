@@ -0,0 +1,321 @@
+//! UDP gossip layer for sharing [`ResponseCache`] entries between zeroclaw
+//! peers running against the same workspace/model, so N independent
+//! instances don't each re-pay tokens for identical prompts.
+//!
+//! Gated behind `[memory] response_cache_gossip = true` with a configured
+//! peer list. On a local `put`, the cache sends a [`GossipPut`] down the
+//! channel attached by [`ResponseCache::attach_gossip`]; this module's
+//! background task picks those up and broadcasts a compact datagram to
+//! every peer. An incoming datagram is applied via
+//! `ResponseCache::apply_from_gossip` -- the same eviction/weight rules as a
+//! local `put` still apply -- and re-broadcast to the rest of the mesh
+//! (excluding the sender), with a short-lived seen-key set deduplicating
+//! re-broadcasts to prevent gossip storms. A digest round lets a freshly
+//! started node, whose key set starts empty, pull the entries it's missing
+//! from peers by key-set comparison rather than waiting to observe every
+//! `put` firsthand.
+//!
+//! `GossipHandle::spawn` is the single entry point: call it once at startup,
+//! after the memory backend's `ResponseCache` has been constructed and
+//! wrapped in an `Arc`.
+//!
+//! Inbound datagrams are only acted on if their source address is in the
+//! configured peer list (`handle_message`) -- anything else is dropped and
+//! logged, since an unauthenticated sender could otherwise inject arbitrary
+//! cache entries or harvest the whole cache via a `Digest` request.
+
+use crate::memory::response_cache::{GossipPut, ResponseCache};
+use chrono::Local;
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use parking_lot::Mutex;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::{debug, warn};
+
+/// UDP datagrams from other zeroclaw/gossip implementations are never
+/// expected to exceed this; anything bigger is dropped rather than
+/// fragmented.
+const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// How long a forwarded/broadcast key is remembered in the dedup set before
+/// it can be re-sent, bounding memory for long-running nodes.
+const SEEN_KEY_TTL_SECS: i64 = 60;
+
+/// A single cache entry being pushed to a peer, either from a local `put` or
+/// in response to a [`GossipMessage::Digest`].
+struct GossipEntry {
+    key: String,
+    model: String,
+    response: String,
+    token_count: u32,
+    remaining_ttl_minutes: i64,
+}
+
+enum GossipMessage {
+    Entry(GossipEntry),
+    /// The set of cache keys a node currently holds, broadcast on startup so
+    /// peers can push back anything this node is missing.
+    Digest { keys: Vec<String> },
+}
+
+impl GossipMessage {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            GossipMessage::Entry(entry) => {
+                buf.push(0u8);
+                write_str(&mut buf, &entry.key);
+                write_str(&mut buf, &entry.model);
+                write_str(&mut buf, &entry.response);
+                buf.extend_from_slice(&entry.token_count.to_be_bytes());
+                buf.extend_from_slice(&entry.remaining_ttl_minutes.to_be_bytes());
+            }
+            GossipMessage::Digest { keys } => {
+                buf.push(1u8);
+                buf.extend_from_slice(&(keys.len() as u32).to_be_bytes());
+                for key in keys {
+                    write_str(&mut buf, key);
+                }
+            }
+        }
+        buf
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut r = ByteReader::new(bytes);
+        match r.read_u8()? {
+            0 => Some(GossipMessage::Entry(GossipEntry {
+                key: r.read_str()?,
+                model: r.read_str()?,
+                response: r.read_str()?,
+                token_count: r.read_u32()?,
+                remaining_ttl_minutes: r.read_i64()?,
+            })),
+            1 => {
+                let count = r.read_u32()?;
+                let mut keys = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    keys.push(r.read_str()?);
+                }
+                Some(GossipMessage::Digest { keys })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn write_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_be_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+/// Cursor over a received datagram; every read returns `None` on truncation
+/// instead of panicking, since the bytes came off the network.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(len)?;
+        let slice = self.bytes.get(self.pos..end)?;
+        self.pos = end;
+        Some(slice)
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        self.take(4).map(|b| u32::from_be_bytes(b.try_into().unwrap()))
+    }
+
+    fn read_i64(&mut self) -> Option<i64> {
+        self.take(8).map(|b| i64::from_be_bytes(b.try_into().unwrap()))
+    }
+
+    fn read_str(&mut self) -> Option<String> {
+        let len = self.read_u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+/// Handle to the UDP gossip subsystem: owns the socket and the configured
+/// peer list, and drives both the outbound broadcast loop and the inbound
+/// receive loop once spawned.
+pub struct GossipHandle {
+    socket: UdpSocket,
+    peers: Vec<SocketAddr>,
+    /// Keys broadcast or forwarded recently, so re-broadcasts of the same
+    /// entry within `SEEN_KEY_TTL_SECS` are suppressed instead of echoing
+    /// around the mesh forever.
+    seen: Mutex<HashMap<String, chrono::DateTime<Local>>>,
+}
+
+impl GossipHandle {
+    /// Bind the gossip socket and spawn the outbound/inbound background
+    /// tasks, wiring `cache` to broadcast every local `put` and to apply
+    /// entries received from peers. Call this once at startup, after `cache`
+    /// has been wrapped in an `Arc`.
+    pub async fn spawn(
+        bind_addr: SocketAddr,
+        peers: Vec<SocketAddr>,
+        cache: Arc<ResponseCache>,
+    ) -> anyhow::Result<Arc<Self>> {
+        let std_socket = std::net::UdpSocket::bind(bind_addr)?;
+        std_socket.set_nonblocking(true)?;
+        let socket = UdpSocket::from_std(std_socket)?;
+
+        let handle = Arc::new(Self {
+            socket,
+            peers,
+            seen: Mutex::new(HashMap::new()),
+        });
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        cache.attach_gossip(tx);
+        tokio::spawn(Arc::clone(&handle).run_outbound(rx));
+        tokio::spawn(Arc::clone(&handle).run_inbound(Arc::clone(&cache)));
+
+        let digest = cache.key_set().into_iter().collect();
+        handle.broadcast_digest(digest).await;
+
+        Ok(handle)
+    }
+
+    /// Record `key` as seen within the dedup window. Returns `true` if this
+    /// is the first time it's been seen recently (i.e. it should be
+    /// forwarded), `false` if it's a duplicate that should be dropped.
+    fn mark_seen(&self, key: &str) -> bool {
+        let now = Local::now();
+        let mut seen = self.seen.lock();
+        seen.retain(|_, ts| now.signed_duration_since(*ts).num_seconds() < SEEN_KEY_TTL_SECS);
+        if seen.contains_key(key) {
+            false
+        } else {
+            seen.insert(key.to_string(), now);
+            true
+        }
+    }
+
+    /// Drain outbound [`GossipPut`]s from local `put`s and broadcast each to
+    /// every peer. Runs until the channel closes (i.e. the cache is dropped).
+    async fn run_outbound(self: Arc<Self>, mut rx: UnboundedReceiver<GossipPut>) {
+        while let Some(put) = rx.recv().await {
+            self.mark_seen(&put.key);
+            let msg = GossipMessage::Entry(GossipEntry {
+                key: put.key,
+                model: put.model,
+                response: put.response,
+                token_count: put.token_count,
+                remaining_ttl_minutes: put.remaining_ttl_minutes,
+            });
+            self.send_to_all(&msg, None).await;
+        }
+    }
+
+    /// Receive loop: applies incoming entries to `cache` and answers
+    /// digests. Intended to run for the lifetime of the process.
+    async fn run_inbound(self: Arc<Self>, cache: Arc<ResponseCache>) {
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        loop {
+            let (len, from) = match self.socket.recv_from(&mut buf).await {
+                Ok(v) => v,
+                Err(e) => {
+                    warn!("Gossip receive error: {e}");
+                    continue;
+                }
+            };
+            let Some(msg) = GossipMessage::decode(&buf[..len]) else {
+                warn!("Dropping malformed gossip datagram from {from}");
+                continue;
+            };
+            self.handle_message(msg, from, &cache).await;
+        }
+    }
+
+    async fn handle_message(&self, msg: GossipMessage, from: SocketAddr, cache: &Arc<ResponseCache>) {
+        if !self.peers.contains(&from) {
+            warn!("Dropping gossip message from unrecognized peer {from}");
+            return;
+        }
+        match msg {
+            GossipMessage::Entry(entry) => {
+                if entry.remaining_ttl_minutes <= 0 {
+                    return;
+                }
+                if !self.mark_seen(&entry.key) {
+                    return;
+                }
+                if let Err(e) = cache.apply_from_gossip(
+                    &entry.key,
+                    &entry.model,
+                    &entry.response,
+                    entry.token_count,
+                    entry.remaining_ttl_minutes,
+                ) {
+                    warn!("Failed to apply gossiped cache entry from {from}: {e}");
+                    return;
+                }
+                debug!("Applied gossiped cache entry from {from}");
+                self.send_to_all(&GossipMessage::Entry(entry), Some(from)).await;
+            }
+            GossipMessage::Digest { keys } => {
+                let digest_keys: HashSet<String> = keys.into_iter().collect();
+                let local_keys = cache.key_set();
+                for key in local_keys.difference(&digest_keys) {
+                    if let Some((model, response, token_count, remaining_ttl_minutes)) =
+                        cache.export_for_gossip(key)
+                    {
+                        let entry = GossipEntry {
+                            key: key.clone(),
+                            model,
+                            response,
+                            token_count,
+                            remaining_ttl_minutes,
+                        };
+                        self.send_to(&GossipMessage::Entry(entry), from).await;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Broadcast a digest of locally resident keys so peers can push back
+    /// anything this node is missing. Called once on startup.
+    async fn broadcast_digest(&self, keys: Vec<String>) {
+        self.send_to_all(&GossipMessage::Digest { keys }, None).await;
+    }
+
+    async fn send_to_all(&self, msg: &GossipMessage, exclude: Option<SocketAddr>) {
+        let encoded = msg.encode();
+        if encoded.len() > MAX_DATAGRAM_SIZE {
+            warn!("Gossip message exceeds max UDP datagram size, dropping");
+            return;
+        }
+        for &peer in &self.peers {
+            if Some(peer) == exclude {
+                continue;
+            }
+            if let Err(e) = self.socket.send_to(&encoded, peer).await {
+                warn!("Failed to gossip to peer {peer}: {e}");
+            }
+        }
+    }
+
+    async fn send_to(&self, msg: &GossipMessage, peer: SocketAddr) {
+        let encoded = msg.encode();
+        if let Err(e) = self.socket.send_to(&encoded, peer).await {
+            warn!("Failed to gossip to peer {peer}: {e}");
+        }
+    }
+}
@@ -1,7 +1,9 @@
 pub mod backend;
 pub mod chunker;
 pub mod cli;
+pub mod conformance;
 pub mod embeddings;
+pub mod gossip;
 pub mod hygiene;
 pub mod response_cache;
 pub mod snapshot;
@@ -12,6 +14,8 @@ pub use backend::{
     classify_memory_backend, default_memory_backend_key, memory_backend_profile,
     selectable_memory_backends, MemoryBackendKind, MemoryBackendProfile,
 };
+pub use conformance::{run_memory_conformance, ConformanceCase, ConformanceReport};
+pub use gossip::GossipHandle;
 pub use response_cache::ResponseCache;
 pub use traits::Memory;
 #[allow(unused_imports)]
@@ -51,11 +55,12 @@ pub fn effective_memory_backend_name(
 pub fn create_oracle_memory(
     conn_manager: &OracleConnectionManager,
 ) -> anyhow::Result<Box<dyn Memory>> {
-    let embedder: Arc<dyn embeddings::EmbeddingProvider> = Arc::new(
-        OracleEmbedding::new(conn_manager.conn(), conn_manager.onnx_model()),
-    );
+    let embedder: Arc<dyn embeddings::EmbeddingProvider> = Arc::new(OracleEmbedding::new(
+        conn_manager.retryable_conn(),
+        conn_manager.onnx_model(),
+    ));
     Ok(Box::new(OracleMemory::new(
-        conn_manager.conn(),
+        conn_manager.pool(),
         conn_manager.agent_id(),
         embedder,
     )))
@@ -72,11 +77,8 @@ pub fn create_oracle_memory_from_config(
 
     // Initialize schema (idempotent — silently skips existing objects).
     {
-        let conn = mgr.conn();
-        let guard = conn
-            .lock()
-            .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
-        crate::oracle::schema::init_schema(&guard, mgr.agent_id())?;
+        let guard = mgr.acquire()?;
+        crate::oracle::schema::init_schema(&guard, mgr.agent_id(), &oracle_config.vector_index)?;
     }
 
     create_oracle_memory(&mgr)
@@ -137,6 +139,28 @@ pub fn create_memory_for_migration(
     create_oracle_memory_from_config(&oracle_config)
 }
 
+/// Create a concrete `OracleMemory` for migration.
+///
+/// Migration needs `OracleMemory::migrate_upsert`, which isn't part of the
+/// `Memory` trait (it runs the whole import as one transaction), so this
+/// returns the concrete type instead of a boxed trait object like the other
+/// factories here.
+pub fn create_oracle_memory_for_migration() -> anyhow::Result<OracleMemory> {
+    let oracle_config = resolve_oracle_config()?;
+    let mgr = OracleConnectionManager::new(&oracle_config)?;
+
+    {
+        let guard = mgr.acquire()?;
+        crate::oracle::schema::init_schema(&guard, mgr.agent_id(), &oracle_config.vector_index)?;
+    }
+
+    let embedder: Arc<dyn embeddings::EmbeddingProvider> = Arc::new(OracleEmbedding::new(
+        mgr.retryable_conn(),
+        mgr.onnx_model(),
+    ));
+    Ok(OracleMemory::new(mgr.pool(), mgr.agent_id(), embedder))
+}
+
 /// Resolve the Oracle config, falling back to environment variables.
 ///
 /// This allows the factory functions (which don't receive a full `Config`)
@@ -174,29 +198,99 @@ fn resolve_oracle_config() -> anyhow::Result<OracleConfig> {
     if let Ok(agent_id) = std::env::var("ZEROORACLAW_ORACLE_AGENT_ID") {
         cfg.agent_id = agent_id;
     }
+    // Consumed by `ConnectionPool::new` (see `oracle::connection`) to size the
+    // pool every Oracle-backed store draws connections from. `pool_min_size`
+    // wins over `pool_max_size` if the two disagree -- the pool clamps
+    // `max_size` up to at least `min_size` rather than erroring.
+    if let Ok(pool_min) = std::env::var("ZEROORACLAW_ORACLE_POOL_MIN_SIZE") {
+        if let Ok(v) = pool_min.parse::<u32>() {
+            cfg.pool_min_size = v;
+        }
+    }
+    if let Ok(pool_max) = std::env::var("ZEROORACLAW_ORACLE_POOL_MAX_SIZE") {
+        if let Ok(v) = pool_max.parse::<u32>() {
+            cfg.pool_max_size = v;
+        }
+    }
+    if let Ok(pool_idle) = std::env::var("ZEROORACLAW_ORACLE_POOL_IDLE_TIMEOUT_SECS") {
+        if let Ok(v) = pool_idle.parse::<u64>() {
+            cfg.pool_idle_timeout_secs = v;
+        }
+    }
+    if let Ok(retries) = std::env::var("ZEROORACLAW_ORACLE_RETRY_MAX_ATTEMPTS") {
+        if let Ok(v) = retries.parse::<u32>() {
+            cfg.retry_max_attempts = v;
+        }
+    }
+    if let Ok(busy_timeout) = std::env::var("ZEROORACLAW_ORACLE_RETRY_BUSY_TIMEOUT_SECS") {
+        if let Ok(v) = busy_timeout.parse::<u64>() {
+            cfg.retry_busy_timeout_secs = v;
+        }
+    }
 
     Ok(cfg)
 }
 
 /// Factory: create an optional response cache from config.
+///
+/// `conn_manager` is only consulted when `[memory] response_cache_persist`
+/// is also set -- it supplies the `OracleResponseCacheStore` that
+/// write-throughs `put`s and rehydrates the cache from `ZERO_RESPONSE_CACHE`
+/// on startup. Passing `None` here (or leaving the flag off) keeps the cache
+/// purely in-memory, matching prior behavior.
 pub fn create_response_cache(
     config: &MemoryConfig,
     workspace_dir: &Path,
+    conn_manager: Option<&OracleConnectionManager>,
 ) -> Option<ResponseCache> {
     if !config.response_cache_enabled {
         return None;
     }
 
-    match ResponseCache::new(
-        workspace_dir,
-        config.response_cache_ttl_minutes,
-        config.response_cache_max_entries,
-    ) {
+    let result = if config.response_cache_persist {
+        match conn_manager {
+            Some(mgr) => {
+                let store = crate::oracle::OracleResponseCacheStore::new(
+                    mgr.retryable_conn(),
+                    mgr.agent_id(),
+                );
+                ResponseCache::new_with_persistence(
+                    workspace_dir,
+                    config.response_cache_ttl_minutes,
+                    config.response_cache_max_entries,
+                    config.response_cache_max_weight,
+                    store,
+                )
+            }
+            None => {
+                tracing::warn!(
+                    "response_cache_persist is set but no Oracle connection manager was supplied; \
+                     falling back to an in-memory-only cache"
+                );
+                ResponseCache::new(
+                    workspace_dir,
+                    config.response_cache_ttl_minutes,
+                    config.response_cache_max_entries,
+                    config.response_cache_max_weight,
+                )
+            }
+        }
+    } else {
+        ResponseCache::new(
+            workspace_dir,
+            config.response_cache_ttl_minutes,
+            config.response_cache_max_entries,
+            config.response_cache_max_weight,
+        )
+    };
+
+    match result {
         Ok(cache) => {
             tracing::info!(
-                "Response cache enabled (TTL: {}min, max: {} entries)",
+                "Response cache enabled (TTL: {}min, max: {} entries, persisted: {})",
                 config.response_cache_ttl_minutes,
-                config.response_cache_max_entries
+                config.response_cache_max_entries,
+                config.response_cache_persist
             );
             Some(cache)
         }
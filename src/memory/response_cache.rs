@@ -4,55 +4,518 @@
 //! SQLite-backed cache. The cache is optional and disabled by default -- users
 //! opt in via `[memory] response_cache_enabled = true`.
 //!
-//! Note: Since we removed the SQLite dependency, this cache is now purely
-//! in-memory (not persisted across restarts). A future version may store
-//! cache entries in Oracle if persistence is desired.
+//! The in-memory map is always the hot path -- reads never hit the database.
+//! Opting into `[memory] response_cache_persist = true` additionally layers an
+//! [`OracleResponseCacheStore`] on top: every `put` write-throughs to
+//! `ZERO_RESPONSE_CACHE`, `new_with_persistence` rehydrates non-expired rows
+//! back into the cache on startup, and `accessed_at`/`hit_count` updates from
+//! `get` are buffered and flushed in batches rather than one round trip per
+//! read.
+//!
+//! Eviction uses a Window-TinyLFU admission policy (the scheme behind
+//! Caffeine/moka) rather than plain LRU: a small "window" segment admits
+//! everything, and anything the window evicts is only let into the main
+//! segmented-LRU region if it's been seen more often (per a count-min
+//! frequency sketch) than that region's current LRU victim. This keeps
+//! frequently-reused prompts resident against a flood of one-hit-wonders,
+//! and every eviction is O(1) via an intrusive doubly-linked list over a
+//! slab of nodes instead of a linear scan for the least-recently-used key.
+//!
+//! `max_entries` bounds the entry count; an optional `max_weight` additionally
+//! bounds the sum of `response.len() + token_count` across resident entries,
+//! so a handful of huge responses can't blow past a memory budget just
+//! because they fit under the entry-count cap. When weight exceeds the
+//! budget, `put` evicts the least-valuable resident entries (window's victim
+//! first, then probation's, then protected's) until it fits again.
+//!
+//! Attaching a [`crate::memory::gossip::GossipHandle`] via
+//! [`ResponseCache::attach_gossip`] additionally broadcasts every local
+//! `put` to configured peers, so several zeroclaw instances sharing a
+//! workspace/model don't each re-pay tokens for identical prompts.
+//!
+//! The store is split into independently-locked shards (a key's hash picks
+//! its shard), so `get`/`put` calls for keys in different shards never
+//! contend -- only truly large caches actually split into more than one
+//! shard, and a small `max_weight` budget caps the shard count further so
+//! each shard's slice of it stays usable; see [`shard_count`]. Within a
+//! shard, `hit_count`/`accessed_at` live
+//! in atomics on the entry itself rather than behind the shard's
+//! `CacheState`, one step closer to lock-free hit accounting even though the
+//! shard lock is still held across a `get` for the Window-TinyLFU position
+//! update (see [`ResponseCache::touch`]).
 
+use crate::oracle::response_cache_store::{AccessUpdate, OracleResponseCacheStore};
 use anyhow::Result;
-use chrono::{Duration, Local};
+use chrono::{DateTime, Duration, Local};
 use parking_lot::Mutex;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use tracing::warn;
+
+/// Number of buffered `accessed_at`/`hit_count` updates before
+/// [`ResponseCache::get`] flushes them to `ZERO_RESPONSE_CACHE` in one batch.
+const ACCESS_FLUSH_BATCH_SIZE: usize = 20;
+
+/// Number of independent hash rows in the count-min frequency sketch.
+const SKETCH_ROWS: usize = 4;
+
+/// Upper bound on the number of shards a cache is split into.
+const MAX_SHARDS: usize = 16;
+
+/// A cache only splits into more than one shard once it's large enough that
+/// each shard still gets at least this many entries -- a small cache (the
+/// common case in unit tests) stays a single shard so its Window-TinyLFU
+/// window/probation/protected segments keep enough room to behave
+/// meaningfully; splitting a 10-entry cache into 16 shards would leave most
+/// shards with no main region at all.
+const MIN_ENTRIES_PER_SHARD: usize = 64;
+
+/// A cache with a `max_weight` budget only splits into more than one shard
+/// once each shard would still get at least this much weight to work with --
+/// mirrors [`MIN_ENTRIES_PER_SHARD`] but on the weight axis. Without this,
+/// `max_entries` alone could pick a shard count that's fine for the entry-
+/// count bound but leaves each shard's slice of a small `max_weight` too
+/// thin to hold even one typical entry, so every `put` evicts itself
+/// immediately and the cache never actually uses its weight budget.
+const MIN_WEIGHT_PER_SHARD: u64 = 4096;
+
+/// How many shards a cache with `max_entries` capacity and an optional
+/// `max_weight` budget should use. The weight budget, when present, can
+/// only shrink the shard count chosen by `max_entries` -- never grow it --
+/// so a shard's slice of `max_weight` never drops below
+/// [`MIN_WEIGHT_PER_SHARD`].
+fn shard_count(max_entries: usize, max_weight: Option<u64>) -> usize {
+    let by_entries = (max_entries / MIN_ENTRIES_PER_SHARD).clamp(1, MAX_SHARDS);
+    let by_weight = max_weight.map_or(MAX_SHARDS, |w| {
+        ((w / MIN_WEIGHT_PER_SHARD) as usize).clamp(1, MAX_SHARDS)
+    });
+    by_entries.min(by_weight)
+}
+
+/// Which shard a key belongs to, out of `num_shards`.
+fn shard_for(key: &str, num_shards: usize) -> usize {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    key.hash(&mut hasher);
+    (hasher.finish() as usize) % num_shards
+}
 
-/// A single cached response entry.
-#[derive(Clone)]
+/// A single cached response entry. `hit_count`/`accessed_at` are atomics so
+/// they can be bumped on every `get` without widening what the shard lock
+/// has to cover.
 struct CacheEntry {
     model: String,
     response: String,
     token_count: u32,
-    created_at: chrono::DateTime<chrono::Local>,
-    accessed_at: chrono::DateTime<chrono::Local>,
-    hit_count: u64,
+    created_at: DateTime<Local>,
+    accessed_at: AtomicI64,
+    hit_count: AtomicU64,
+    /// Per-entry TTL override in minutes, set via
+    /// [`ResponseCache::put_with_ttl`]. `None` falls back to the cache-wide
+    /// `ttl_minutes` configured at construction.
+    ttl_minutes: Option<i64>,
+}
+
+/// Which segmented-LRU region a node currently lives in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    /// Small admission window -- every new key lands here first.
+    Window,
+    /// Main region, probationary half -- admitted from the window but not
+    /// yet re-accessed.
+    Probation,
+    /// Main region, protected half -- promoted after a repeat access.
+    Protected,
+}
+
+/// One slot in the node slab backing the intrusive doubly-linked lists.
+/// `prev`/`next` are relative to whichever `Segment` list the node
+/// currently belongs to; a node belongs to exactly one list at a time.
+struct Node {
+    key: String,
+    entry: CacheEntry,
+    prev: Option<usize>,
+    next: Option<usize>,
+    segment: Segment,
+}
+
+/// An intrusive doubly-linked list over [`Node`] slab indices. `head` is the
+/// most-recently-used end, `tail` the least-recently-used end.
+#[derive(Default)]
+struct LruList {
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+}
+
+fn list_push_front(nodes: &mut [Node], list: &mut LruList, idx: usize) {
+    nodes[idx].prev = None;
+    nodes[idx].next = list.head;
+    if let Some(head) = list.head {
+        nodes[head].prev = Some(idx);
+    }
+    list.head = Some(idx);
+    if list.tail.is_none() {
+        list.tail = Some(idx);
+    }
+    list.len += 1;
+}
+
+fn list_remove(nodes: &mut [Node], list: &mut LruList, idx: usize) {
+    let prev = nodes[idx].prev;
+    let next = nodes[idx].next;
+    match prev {
+        Some(p) => nodes[p].next = next,
+        None => list.head = next,
+    }
+    match next {
+        Some(n) => nodes[n].prev = prev,
+        None => list.tail = prev,
+    }
+    nodes[idx].prev = None;
+    nodes[idx].next = None;
+    list.len -= 1;
+}
+
+fn list_move_to_front(nodes: &mut [Node], list: &mut LruList, idx: usize) {
+    if list.head == Some(idx) {
+        return;
+    }
+    list_remove(nodes, list, idx);
+    list_push_front(nodes, list, idx);
+}
+
+fn list_pop_back(nodes: &mut [Node], list: &mut LruList) -> Option<usize> {
+    let idx = list.tail?;
+    list_remove(nodes, list, idx);
+    Some(idx)
+}
+
+/// Count-min sketch of recent key access frequency, saturating at `u8::MAX`
+/// per counter and halved ("aged") once total increments cross
+/// `reset_threshold`, so frequency reflects recent activity rather than a
+/// prompt's entire lifetime.
+struct CountMinSketch {
+    width: usize,
+    rows: [Vec<u8>; SKETCH_ROWS],
+    additions: u64,
+    reset_threshold: u64,
+}
+
+impl CountMinSketch {
+    fn new(max_entries: usize) -> Self {
+        let width = (max_entries.max(4) * 4).next_power_of_two();
+        Self {
+            width,
+            rows: std::array::from_fn(|_| vec![0u8; width]),
+            additions: 0,
+            reset_threshold: width as u64 * 10,
+        }
+    }
+
+    fn slot(&self, key: &str, row: usize) -> usize {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        row.hash(&mut hasher);
+        key.hash(&mut hasher);
+        (hasher.finish() as usize) & (self.width - 1)
+    }
+
+    fn increment(&mut self, key: &str) {
+        for row in 0..SKETCH_ROWS {
+            let slot = self.slot(key, row);
+            let counter = &mut self.rows[row][slot];
+            if *counter < u8::MAX {
+                *counter += 1;
+            }
+        }
+        self.additions += 1;
+        if self.additions > self.reset_threshold {
+            self.reset();
+        }
+    }
+
+    fn estimate(&self, key: &str) -> u8 {
+        (0..SKETCH_ROWS)
+            .map(|row| self.rows[row][self.slot(key, row)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn reset(&mut self) {
+        for row in &mut self.rows {
+            for counter in row.iter_mut() {
+                *counter /= 2;
+            }
+        }
+        self.additions /= 2;
+    }
 }
 
-/// Response cache backed by an in-memory HashMap.
+/// A cache entry bound for [`crate::memory::gossip::GossipHandle`], carrying
+/// everything needed to apply it on a peer via `ResponseCache::apply_from_gossip`.
+pub struct GossipPut {
+    pub key: String,
+    pub model: String,
+    pub response: String,
+    pub token_count: u32,
+    pub remaining_ttl_minutes: i64,
+}
+
+/// Weight of a cache entry for [`CacheState::total_weight`] purposes:
+/// response bytes plus token count, so a cache of a thousand huge
+/// multi-kilotoken responses is bounded the same as a thousand tiny ones.
+fn entry_weight(entry: &CacheEntry) -> u64 {
+    entry.response.len() as u64 + u64::from(entry.token_count)
+}
+
+/// All mutable cache state, guarded by a single lock so the slab, index, and
+/// the three segment lists stay consistent.
+struct CacheState {
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    index: HashMap<String, usize>,
+    window: LruList,
+    probation: LruList,
+    protected: LruList,
+    sketch: CountMinSketch,
+    /// Running sum of `entry_weight` across every resident entry, kept in
+    /// sync by `alloc`/`evict` so `put` can enforce `max_weight` without a
+    /// full scan.
+    total_weight: u64,
+}
+
+impl CacheState {
+    fn new(max_entries: usize) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            index: HashMap::new(),
+            window: LruList::default(),
+            probation: LruList::default(),
+            protected: LruList::default(),
+            sketch: CountMinSketch::new(max_entries),
+            total_weight: 0,
+        }
+    }
+
+    /// Allocate a slab slot for `key`/`entry`, reusing a freed slot if one is
+    /// available, and record it in `index`. The caller is responsible for
+    /// pushing the returned index onto the right segment list.
+    fn alloc(&mut self, key: String, entry: CacheEntry) -> usize {
+        self.total_weight += entry_weight(&entry);
+        let idx = match self.free.pop() {
+            Some(idx) => {
+                self.nodes[idx] = Node {
+                    key: key.clone(),
+                    entry,
+                    prev: None,
+                    next: None,
+                    segment: Segment::Window,
+                };
+                idx
+            }
+            None => {
+                self.nodes.push(Node {
+                    key: key.clone(),
+                    entry,
+                    prev: None,
+                    next: None,
+                    segment: Segment::Window,
+                });
+                self.nodes.len() - 1
+            }
+        };
+        self.index.insert(key, idx);
+        idx
+    }
+
+    /// Replace the entry stored at `idx` in place, keeping `total_weight` in
+    /// sync with the old entry's weight leaving and the new one's arriving.
+    fn replace_entry(&mut self, idx: usize, entry: CacheEntry) {
+        self.total_weight -= entry_weight(&self.nodes[idx].entry);
+        self.total_weight += entry_weight(&entry);
+        self.nodes[idx].entry = entry;
+    }
+
+    /// Fully remove the node at `idx` from its current list, the index, and
+    /// free its slab slot.
+    fn evict(&mut self, idx: usize) {
+        let list = match self.nodes[idx].segment {
+            Segment::Window => &mut self.window,
+            Segment::Probation => &mut self.probation,
+            Segment::Protected => &mut self.protected,
+        };
+        list_remove(&mut self.nodes, list, idx);
+        self.total_weight -= entry_weight(&self.nodes[idx].entry);
+        let key = std::mem::take(&mut self.nodes[idx].key);
+        self.index.remove(&key);
+        self.free.push(idx);
+    }
+
+    /// Evict the single least-valuable resident entry (window's LRU victim
+    /// first, then probation's, then protected's) to make room under a
+    /// weight bound. Returns `false` if the cache is already empty.
+    fn evict_one_for_weight(&mut self) -> bool {
+        let idx = self
+            .window
+            .tail
+            .or(self.probation.tail)
+            .or(self.protected.tail);
+        match idx {
+            Some(idx) => {
+                self.evict(idx);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Response cache backed by a Window-TinyLFU admission policy.
 ///
 /// Replaces the previous SQLite-backed cache. Lives alongside the workspace
-/// for configuration purposes but does not persist across restarts.
+/// for configuration purposes. Persists across restarts only when built via
+/// [`ResponseCache::new_with_persistence`].
 pub struct ResponseCache {
-    entries: Mutex<HashMap<String, CacheEntry>>,
+    /// Independently-locked shards; `shard_for(key, shards.len())` picks
+    /// which one a key lives in. Each shard runs its own Window-TinyLFU
+    /// instance sized off `per_shard_capacity`, so only one shard's lock is
+    /// ever held at a time.
+    shards: Vec<Mutex<CacheState>>,
     #[allow(dead_code)]
     db_path: PathBuf,
     ttl_minutes: i64,
+    /// The originally requested capacity; kept only so callers reading the
+    /// struct back (e.g. future diagnostics) see what was asked for. Actual
+    /// admission is governed by `per_shard_capacity`.
+    #[allow(dead_code)]
     max_entries: usize,
+    per_shard_capacity: usize,
+    /// Optional bound on `CacheState::total_weight` (response bytes + token
+    /// count), enforced per-shard in `put` in addition to `max_entries`.
+    /// `None` means unbounded -- distinct from `max_entries == 0`, which
+    /// already means "store nothing". Split evenly across shards, so the
+    /// aggregate bound across the whole cache is approximately `max_weight`
+    /// rather than exact.
+    #[allow(dead_code)]
+    max_weight: Option<u64>,
+    per_shard_max_weight: Option<u64>,
+    window_capacity: usize,
+    probation_capacity: usize,
+    protected_capacity: usize,
+    persistence: Option<OracleResponseCacheStore>,
+    pending_access_updates: Mutex<Vec<AccessUpdate>>,
+    /// Outbound channel to a [`crate::memory::gossip::GossipHandle`], set via
+    /// [`Self::attach_gossip`]. `None` means gossip isn't enabled for this
+    /// cache.
+    gossip_tx: Mutex<Option<tokio::sync::mpsc::UnboundedSender<GossipPut>>>,
 }
 
 impl ResponseCache {
-    /// Open (or create) the response cache.
-    pub fn new(workspace_dir: &Path, ttl_minutes: u32, max_entries: usize) -> Result<Self> {
+    /// Open (or create) the response cache, purely in-memory.
+    ///
+    /// `max_weight` bounds the sum of `response.len() + token_count` across
+    /// resident entries (in addition to `max_entries`); pass `None` to leave
+    /// it unbounded.
+    pub fn new(
+        workspace_dir: &Path,
+        ttl_minutes: u32,
+        max_entries: usize,
+        max_weight: Option<u64>,
+    ) -> Result<Self> {
         let db_dir = workspace_dir.join("memory");
         std::fs::create_dir_all(&db_dir)?;
         let db_path = db_dir.join("response_cache.db");
 
+        let num_shards = shard_count(max_entries, max_weight);
+        let per_shard_capacity = max_entries / num_shards;
+        let per_shard_max_weight = max_weight.map(|w| w / num_shards as u64);
+
+        // Window ~1% of capacity (Caffeine's default); the remainder is the
+        // main segmented-LRU region, itself split 20% probation / 80%
+        // protected. Window and probation get at least one slot each, except
+        // that a per-shard capacity of 0 leaves every segment at 0, which
+        // disables admission entirely rather than rounding up to a 1-entry
+        // shard.
+        let window_capacity = if per_shard_capacity == 0 {
+            0
+        } else {
+            (per_shard_capacity / 100).max(1)
+        };
+        let main_capacity = per_shard_capacity.saturating_sub(window_capacity);
+        let probation_capacity = if main_capacity == 0 {
+            0
+        } else {
+            (main_capacity / 5).max(1)
+        };
+        let protected_capacity = main_capacity.saturating_sub(probation_capacity);
+
+        let shards = (0..num_shards)
+            .map(|_| Mutex::new(CacheState::new(per_shard_capacity)))
+            .collect();
+
         Ok(Self {
-            entries: Mutex::new(HashMap::new()),
+            shards,
             db_path,
             ttl_minutes: i64::from(ttl_minutes),
             max_entries,
+            per_shard_capacity,
+            max_weight,
+            per_shard_max_weight,
+            window_capacity,
+            probation_capacity,
+            protected_capacity,
+            persistence: None,
+            pending_access_updates: Mutex::new(Vec::new()),
+            gossip_tx: Mutex::new(None),
         })
     }
 
+    /// The shard `key` belongs to.
+    fn shard(&self, key: &str) -> &Mutex<CacheState> {
+        &self.shards[shard_for(key, self.shards.len())]
+    }
+
+    /// Open the response cache with Oracle-backed persistence: rehydrates
+    /// non-expired rows from `ZERO_RESPONSE_CACHE` back into the cache, and
+    /// write-throughs every subsequent `put`.
+    pub fn new_with_persistence(
+        workspace_dir: &Path,
+        ttl_minutes: u32,
+        max_entries: usize,
+        max_weight: Option<u64>,
+        store: OracleResponseCacheStore,
+    ) -> Result<Self> {
+        let mut cache = Self::new(workspace_dir, ttl_minutes, max_entries, max_weight)?;
+
+        let mut rehydrated = store.rehydrate(cache.ttl_minutes);
+        // Most-recently-accessed first, so admission ties (frequency sketch
+        // starts empty) favor the rows that mattered most before restart.
+        rehydrated.sort_by_key(|e| std::cmp::Reverse(e.accessed_at));
+        rehydrated.truncate(max_entries);
+
+        for persisted in rehydrated {
+            let entry = CacheEntry {
+                model: persisted.model,
+                response: persisted.response,
+                token_count: persisted.token_count,
+                created_at: persisted.created_at,
+                accessed_at: AtomicI64::new(persisted.accessed_at.timestamp_millis()),
+                hit_count: AtomicU64::new(persisted.hit_count),
+                // Per-entry TTL overrides aren't persisted -- a rehydrated
+                // entry falls back to the cache-wide `ttl_minutes`.
+                ttl_minutes: None,
+            };
+            let mut state = cache.shard(&persisted.key).lock();
+            cache.insert_new(&mut state, persisted.key, entry);
+        }
+
+        cache.persistence = Some(store);
+        Ok(cache)
+    }
+
     /// Build a deterministic cache key from model + system prompt + user prompt.
     pub fn cache_key(model: &str, system_prompt: Option<&str>, user_prompt: &str) -> String {
         let mut hasher = Sha256::new();
@@ -68,82 +531,317 @@ impl ResponseCache {
     }
 
     /// Look up a cached response. Returns `None` on miss or expired entry.
+    ///
+    /// An entry stored via [`Self::put_with_ttl`] is checked against its own
+    /// TTL instead of the cache-wide `ttl_minutes`.
     pub fn get(&self, key: &str) -> Result<Option<String>> {
         let now = Local::now();
-        let cutoff = now - Duration::minutes(self.ttl_minutes);
-        let mut entries = self.entries.lock();
-
-        if let Some(entry) = entries.get_mut(key) {
-            if entry.created_at > cutoff {
-                entry.hit_count += 1;
-                entry.accessed_at = now;
-                return Ok(Some(entry.response.clone()));
+        let mut state = self.shard(key).lock();
+
+        let Some(&idx) = state.index.get(key) else {
+            return Ok(None);
+        };
+
+        let ttl_minutes = state.nodes[idx].entry.ttl_minutes.unwrap_or(self.ttl_minutes);
+        let cutoff = now - Duration::minutes(ttl_minutes);
+        if state.nodes[idx].entry.created_at <= cutoff {
+            state.evict(idx);
+            return Ok(None);
+        }
+
+        let hit_count = state.nodes[idx].entry.hit_count.fetch_add(1, Ordering::Relaxed) + 1;
+        state.nodes[idx]
+            .entry
+            .accessed_at
+            .store(now.timestamp_millis(), Ordering::Relaxed);
+        let response = state.nodes[idx].entry.response.clone();
+
+        state.sketch.increment(key);
+        self.touch(&mut state, idx);
+        drop(state);
+
+        self.queue_access_update(key, now, hit_count);
+        Ok(Some(response))
+    }
+
+    /// Promote/refresh `idx` after an access: window and protected entries
+    /// just move to the front of their own list; a probation entry is
+    /// promoted into protected (demoting protected's LRU back to probation
+    /// if that pushes protected over capacity), matching W-TinyLFU's
+    /// segmented-LRU admission.
+    fn touch(&self, state: &mut CacheState, idx: usize) {
+        match state.nodes[idx].segment {
+            Segment::Window => list_move_to_front(&mut state.nodes, &mut state.window, idx),
+            Segment::Protected => list_move_to_front(&mut state.nodes, &mut state.protected, idx),
+            Segment::Probation => {
+                list_remove(&mut state.nodes, &mut state.probation, idx);
+                state.nodes[idx].segment = Segment::Protected;
+                list_push_front(&mut state.nodes, &mut state.protected, idx);
+
+                if state.protected.len > self.protected_capacity {
+                    if let Some(demoted) = list_pop_back(&mut state.nodes, &mut state.protected) {
+                        state.nodes[demoted].segment = Segment::Probation;
+                        list_push_front(&mut state.nodes, &mut state.probation, demoted);
+                    }
+                }
             }
-            // Expired -- remove it
-            entries.remove(key);
         }
+    }
+
+    /// Insert a brand-new key into the window segment, cascading any
+    /// window overflow through TinyLFU admission into the main region.
+    fn insert_new(&self, state: &mut CacheState, key: String, entry: CacheEntry) {
+        if self.per_shard_capacity == 0 {
+            return;
+        }
+
+        state.sketch.increment(&key);
+        let idx = state.alloc(key, entry);
+        list_push_front(&mut state.nodes, &mut state.window, idx);
 
-        Ok(None)
+        while state.window.len > self.window_capacity {
+            let candidate = list_pop_back(&mut state.nodes, &mut state.window)
+                .expect("window.len > 0 implies a tail node exists");
+            state.nodes[candidate].segment = Segment::Probation;
+            self.admit_to_main(state, candidate);
+        }
     }
 
-    /// Store a response in the cache.
+    /// Decide whether a window-evicted `candidate` is admitted into the
+    /// main region: freely if probation has room, otherwise only if its
+    /// sketch frequency beats probation's current LRU victim -- otherwise
+    /// the candidate itself is the one evicted.
+    fn admit_to_main(&self, state: &mut CacheState, candidate: usize) {
+        if self.probation_capacity == 0 {
+            state.evict(candidate);
+            return;
+        }
+
+        if state.probation.len < self.probation_capacity {
+            list_push_front(&mut state.nodes, &mut state.probation, candidate);
+            return;
+        }
+
+        let victim = state
+            .probation
+            .tail
+            .expect("probation at capacity implies a tail node exists");
+        let candidate_freq = state.sketch.estimate(&state.nodes[candidate].key);
+        let victim_freq = state.sketch.estimate(&state.nodes[victim].key);
+
+        if candidate_freq > victim_freq {
+            state.evict(victim);
+            list_push_front(&mut state.nodes, &mut state.probation, candidate);
+        } else {
+            state.evict(candidate);
+        }
+    }
+
+    /// Buffer an `accessed_at`/`hit_count` update for `key` and flush the
+    /// buffer in one batch once it reaches [`ACCESS_FLUSH_BATCH_SIZE`],
+    /// rather than round-tripping to Oracle on every `get`. No-op when
+    /// persistence isn't enabled.
+    fn queue_access_update(&self, key: &str, accessed_at: DateTime<Local>, hit_count: u64) {
+        let Some(store) = self.persistence.as_ref() else {
+            return;
+        };
+
+        let mut pending = self.pending_access_updates.lock();
+        pending.push(AccessUpdate {
+            key: key.to_string(),
+            accessed_at,
+            hit_count,
+        });
+
+        if pending.len() >= ACCESS_FLUSH_BATCH_SIZE {
+            let batch = std::mem::take(&mut *pending);
+            drop(pending);
+            store.flush_access_updates(&batch);
+        }
+    }
+
+    /// Store a response in the cache, expiring it per the cache-wide
+    /// `ttl_minutes` configured at construction.
     pub fn put(&self, key: &str, model: &str, response: &str, token_count: u32) -> Result<()> {
+        self.put_with_ttl(key, model, response, token_count, None)
+    }
+
+    /// Store a response in the cache with a per-entry TTL override in
+    /// minutes, checked by `get` instead of the cache-wide `ttl_minutes`.
+    /// Pass `None` to fall back to the cache-wide TTL, same as [`Self::put`].
+    ///
+    /// Useful for deterministic prompts worth retaining far longer than the
+    /// default, or volatile ones ("what's the latest...") that should expire
+    /// sooner than it.
+    ///
+    /// If a gossip channel is attached (see [`Self::attach_gossip`]), this
+    /// also broadcasts the entry to peers; use [`Self::apply_from_gossip`]
+    /// instead when applying an entry that already came from a peer, to
+    /// avoid re-broadcasting it.
+    pub fn put_with_ttl(
+        &self,
+        key: &str,
+        model: &str,
+        response: &str,
+        token_count: u32,
+        ttl_minutes: Option<i64>,
+    ) -> Result<()> {
+        self.apply(key, model, response, token_count, ttl_minutes)?;
+        self.notify_gossip(key, model, response, token_count, ttl_minutes);
+        Ok(())
+    }
+
+    /// Apply an entry received from a gossip peer, exactly as `put_with_ttl`
+    /// would locally, but without re-broadcasting it (the gossip receive
+    /// loop handles mesh propagation itself so the dedup/seen-key logic
+    /// stays in one place).
+    pub(crate) fn apply_from_gossip(
+        &self,
+        key: &str,
+        model: &str,
+        response: &str,
+        token_count: u32,
+        remaining_ttl_minutes: i64,
+    ) -> Result<()> {
+        self.apply(key, model, response, token_count, Some(remaining_ttl_minutes))
+    }
+
+    /// Attach an outbound gossip channel: every subsequent `put`/`put_with_ttl`
+    /// will also send a [`GossipPut`] down this channel for
+    /// [`crate::memory::gossip::GossipHandle`] to broadcast to peers.
+    pub fn attach_gossip(&self, tx: tokio::sync::mpsc::UnboundedSender<GossipPut>) {
+        *self.gossip_tx.lock() = Some(tx);
+    }
+
+    /// Snapshot of all resident cache keys, used by the gossip
+    /// digest-exchange round to find what a peer is missing.
+    pub fn key_set(&self) -> std::collections::HashSet<String> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.lock().index.keys().cloned().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Export a resident entry's gossip-relevant fields as
+    /// `(model, response, token_count, remaining_ttl_minutes)`. Returns
+    /// `None` if the key isn't resident, or has already expired under its
+    /// own effective TTL (so gossip never hands out stale entries).
+    pub fn export_for_gossip(&self, key: &str) -> Option<(String, String, u32, i64)> {
+        let state = self.shard(key).lock();
+        let idx = *state.index.get(key)?;
+        let entry = &state.nodes[idx].entry;
+        let ttl_minutes = entry.ttl_minutes.unwrap_or(self.ttl_minutes);
+        let elapsed_minutes = (Local::now() - entry.created_at).num_minutes();
+        let remaining = ttl_minutes - elapsed_minutes;
+        if remaining <= 0 {
+            return None;
+        }
+        Some((
+            entry.model.clone(),
+            entry.response.clone(),
+            entry.token_count,
+            remaining,
+        ))
+    }
+
+    fn notify_gossip(
+        &self,
+        key: &str,
+        model: &str,
+        response: &str,
+        token_count: u32,
+        ttl_minutes: Option<i64>,
+    ) {
+        let Some(tx) = self.gossip_tx.lock().as_ref().cloned() else {
+            return;
+        };
+        let remaining_ttl_minutes = ttl_minutes.unwrap_or(self.ttl_minutes);
+        let put = GossipPut {
+            key: key.to_string(),
+            model: model.to_string(),
+            response: response.to_string(),
+            token_count,
+            remaining_ttl_minutes,
+        };
+        if tx.send(put).is_err() {
+            warn!("Gossip outbound channel closed; dropping broadcast for '{key}'");
+        }
+    }
+
+    fn apply(
+        &self,
+        key: &str,
+        model: &str,
+        response: &str,
+        token_count: u32,
+        ttl_minutes: Option<i64>,
+    ) -> Result<()> {
         let now = Local::now();
-        let cutoff = now - Duration::minutes(self.ttl_minutes);
-        let mut entries = self.entries.lock();
-
-        // Insert or replace
-        entries.insert(
-            key.to_string(),
-            CacheEntry {
-                model: model.to_string(),
-                response: response.to_string(),
-                token_count,
-                created_at: now,
-                accessed_at: now,
-                hit_count: 0,
-            },
-        );
+        let entry = CacheEntry {
+            model: model.to_string(),
+            response: response.to_string(),
+            token_count,
+            created_at: now,
+            accessed_at: AtomicI64::new(now.timestamp_millis()),
+            hit_count: AtomicU64::new(0),
+            ttl_minutes,
+        };
 
-        // Evict expired entries
-        entries.retain(|_, entry| entry.created_at > cutoff);
-
-        // LRU eviction if over max_entries
-        while entries.len() > self.max_entries {
-            // Find the least recently accessed entry
-            let lru_key = entries
-                .iter()
-                .min_by_key(|(_, entry)| entry.accessed_at)
-                .map(|(k, _)| k.clone());
-
-            if let Some(k) = lru_key {
-                entries.remove(&k);
-            } else {
-                break;
+        let mut state = self.shard(key).lock();
+        if let Some(&idx) = state.index.get(key) {
+            state.replace_entry(idx, entry);
+            state.sketch.increment(key);
+            self.touch(&mut state, idx);
+        } else {
+            self.insert_new(&mut state, key.to_string(), entry);
+        }
+
+        if let Some(max_weight) = self.per_shard_max_weight {
+            while state.total_weight > max_weight {
+                if !state.evict_one_for_weight() {
+                    break;
+                }
             }
         }
+        drop(state);
+
+        if let Some(store) = self.persistence.as_ref() {
+            store.write_through(key, model, response, token_count);
+        }
 
         Ok(())
     }
 
-    /// Return cache statistics: (total_entries, total_hits, total_tokens_saved).
-    pub fn stats(&self) -> Result<(usize, u64, u64)> {
-        let entries = self.entries.lock();
-        let count = entries.len();
-        let hits: u64 = entries.values().map(|e| e.hit_count).sum();
-        let tokens_saved: u64 = entries
-            .values()
-            .map(|e| u64::from(e.token_count) * e.hit_count)
-            .sum();
-
-        Ok((count, hits, tokens_saved))
+    /// Return cache statistics: (total_entries, total_hits, total_tokens_saved, total_weight),
+    /// folded across every shard.
+    pub fn stats(&self) -> Result<(usize, u64, u64, u64)> {
+        let mut count = 0usize;
+        let mut hits = 0u64;
+        let mut tokens_saved = 0u64;
+        let mut weight = 0u64;
+        for shard in &self.shards {
+            let state = shard.lock();
+            count += state.index.len();
+            weight += state.total_weight;
+            for &idx in state.index.values() {
+                let entry = &state.nodes[idx].entry;
+                let entry_hits = entry.hit_count.load(Ordering::Relaxed);
+                hits += entry_hits;
+                tokens_saved += u64::from(entry.token_count) * entry_hits;
+            }
+        }
+        Ok((count, hits, tokens_saved, weight))
     }
 
-    /// Wipe the entire cache (useful for `zeroclaw cache clear`).
+    /// Wipe the entire cache (useful for `zeroclaw cache clear`), across
+    /// every shard.
     pub fn clear(&self) -> Result<usize> {
-        let mut entries = self.entries.lock();
-        let count = entries.len();
-        entries.clear();
+        let mut count = 0usize;
+        for shard in &self.shards {
+            let mut state = shard.lock();
+            count += state.index.len();
+            *state = CacheState::new(self.per_shard_capacity);
+        }
         Ok(count)
     }
 }
@@ -155,7 +853,7 @@ mod tests {
 
     fn temp_cache(ttl_minutes: u32) -> (TempDir, ResponseCache) {
         let tmp = TempDir::new().unwrap();
-        let cache = ResponseCache::new(tmp.path(), ttl_minutes, 1000).unwrap();
+        let cache = ResponseCache::new(tmp.path(), ttl_minutes, 1000, None).unwrap();
         (tmp, cache)
     }
 
@@ -236,7 +934,7 @@ mod tests {
             let _ = cache.get(&key).unwrap();
         }
 
-        let (_, total_hits, _) = cache.stats().unwrap();
+        let (_, total_hits, _, _) = cache.stats().unwrap();
         assert_eq!(total_hits, 3);
     }
 
@@ -252,14 +950,14 @@ mod tests {
             let _ = cache.get(&key).unwrap();
         }
 
-        let (_, _, tokens_saved) = cache.stats().unwrap();
+        let (_, _, tokens_saved, _) = cache.stats().unwrap();
         assert_eq!(tokens_saved, 500);
     }
 
     #[test]
     fn lru_eviction() {
         let tmp = TempDir::new().unwrap();
-        let cache = ResponseCache::new(tmp.path(), 60, 3).unwrap(); // max 3 entries
+        let cache = ResponseCache::new(tmp.path(), 60, 3, None).unwrap(); // max 3 entries
 
         for i in 0..5 {
             let key = ResponseCache::cache_key("gpt-4", None, &format!("prompt {i}"));
@@ -268,7 +966,7 @@ mod tests {
                 .unwrap();
         }
 
-        let (count, _, _) = cache.stats().unwrap();
+        let (count, _, _, _) = cache.stats().unwrap();
         assert!(count <= 3, "Should have at most 3 entries after eviction");
     }
 
@@ -286,17 +984,18 @@ mod tests {
         let cleared = cache.clear().unwrap();
         assert_eq!(cleared, 10);
 
-        let (count, _, _) = cache.stats().unwrap();
+        let (count, _, _, _) = cache.stats().unwrap();
         assert_eq!(count, 0);
     }
 
     #[test]
     fn stats_empty_cache() {
         let (_tmp, cache) = temp_cache(60);
-        let (count, hits, tokens) = cache.stats().unwrap();
+        let (count, hits, tokens, weight) = cache.stats().unwrap();
         assert_eq!(count, 0);
         assert_eq!(hits, 0);
         assert_eq!(tokens, 0);
+        assert_eq!(weight, 0);
     }
 
     #[test]
@@ -310,7 +1009,7 @@ mod tests {
         let result = cache.get(&key).unwrap();
         assert_eq!(result.as_deref(), Some("answer v2"));
 
-        let (count, _, _) = cache.stats().unwrap();
+        let (count, _, _, _) = cache.stats().unwrap();
         assert_eq!(count, 1);
     }
 
@@ -330,20 +1029,20 @@ mod tests {
     #[test]
     fn cache_handles_zero_max_entries() {
         let tmp = TempDir::new().unwrap();
-        let cache = ResponseCache::new(tmp.path(), 60, 0).unwrap();
+        let cache = ResponseCache::new(tmp.path(), 60, 0, None).unwrap();
 
         let key = ResponseCache::cache_key("gpt-4", None, "test");
         // Should not panic even with max_entries=0
         cache.put(&key, "gpt-4", "response", 10).unwrap();
 
-        let (count, _, _) = cache.stats().unwrap();
+        let (count, _, _, _) = cache.stats().unwrap();
         assert_eq!(count, 0, "cache with max_entries=0 should evict everything");
     }
 
     #[test]
     fn cache_concurrent_reads_no_panic() {
         let tmp = TempDir::new().unwrap();
-        let cache = std::sync::Arc::new(ResponseCache::new(tmp.path(), 60, 100).unwrap());
+        let cache = std::sync::Arc::new(ResponseCache::new(tmp.path(), 60, 100, None).unwrap());
 
         let key = ResponseCache::cache_key("gpt-4", None, "concurrent");
         cache.put(&key, "gpt-4", "response", 10).unwrap();
@@ -361,7 +1060,144 @@ mod tests {
             handle.join().unwrap();
         }
 
-        let (_, hits, _) = cache.stats().unwrap();
+        let (_, hits, _, _) = cache.stats().unwrap();
         assert_eq!(hits, 10, "all concurrent reads should register as hits");
     }
+
+    #[test]
+    fn frequently_accessed_key_survives_a_flood_of_one_hit_wonders() {
+        let tmp = TempDir::new().unwrap();
+        // Window capacity 1, main capacity 9 (1 probation + 8 protected):
+        // small enough that a flood of unique keys will repeatedly contend
+        // for the one probation slot.
+        let cache = ResponseCache::new(tmp.path(), 60, 10, None).unwrap();
+
+        let hot_key = ResponseCache::cache_key("gpt-4", None, "hot prompt");
+        cache.put(&hot_key, "gpt-4", "hot response", 10).unwrap();
+        // Repeated access raises the hot key's sketch frequency, so once the
+        // flood pushes it out of the window into probation it wins every
+        // admission contest against the one-hit keys.
+        for _ in 0..20 {
+            let _ = cache.get(&hot_key).unwrap();
+        }
+
+        // Flood with one-hit-wonder keys, never re-accessed.
+        for i in 0..200 {
+            let key = ResponseCache::cache_key("gpt-4", None, &format!("flood {i}"));
+            cache.put(&key, "gpt-4", "flood response", 10).unwrap();
+        }
+
+        let result = cache.get(&hot_key).unwrap();
+        assert_eq!(
+            result.as_deref(),
+            Some("hot response"),
+            "a frequently-accessed entry should survive a flood of unique one-hit keys"
+        );
+    }
+
+    #[test]
+    fn promotion_moves_probation_entry_to_protected_on_repeat_access() {
+        let tmp = TempDir::new().unwrap();
+        let cache = ResponseCache::new(tmp.path(), 60, 10, None).unwrap();
+        let key = ResponseCache::cache_key("gpt-4", None, "promote me");
+
+        cache.put(&key, "gpt-4", "response", 10).unwrap();
+        // First put lands the node in the window; force it into probation by
+        // evicting the window via a second, distinct insertion.
+        let filler = ResponseCache::cache_key("gpt-4", None, "filler");
+        cache.put(&filler, "gpt-4", "filler response", 10).unwrap();
+
+        {
+            let state = cache.shard(&key).lock();
+            let idx = state.index[&key];
+            assert_eq!(state.nodes[idx].segment, Segment::Probation);
+        }
+
+        // A repeat access should promote it to the protected segment.
+        let _ = cache.get(&key).unwrap();
+        let state = cache.shard(&key).lock();
+        let idx = state.index[&key];
+        assert_eq!(state.nodes[idx].segment, Segment::Protected);
+    }
+
+    #[test]
+    fn max_weight_evicts_entries_once_budget_is_exceeded() {
+        let tmp = TempDir::new().unwrap();
+        // max_entries is generous so only the weight bound constrains this
+        // cache; each entry below weighs 100 (50-char response + 50 tokens).
+        let cache = ResponseCache::new(tmp.path(), 60, 1000, Some(250)).unwrap();
+
+        let response = "x".repeat(50);
+        for i in 0..5 {
+            let key = ResponseCache::cache_key("gpt-4", None, &format!("prompt {i}"));
+            cache.put(&key, "gpt-4", &response, 50).unwrap();
+        }
+
+        let (count, _, _, weight) = cache.stats().unwrap();
+        assert!(
+            weight <= 250,
+            "total weight {weight} should be bounded by max_weight"
+        );
+        assert!(count <= 2, "only a couple of 100-weight entries should fit under a 250 budget");
+        assert!(count > 0, "the weight budget should still admit entries, not evict everything");
+    }
+
+    #[test]
+    fn max_weight_none_is_unbounded() {
+        let tmp = TempDir::new().unwrap();
+        let cache = ResponseCache::new(tmp.path(), 60, 1000, None).unwrap();
+
+        let response = "x".repeat(10_000);
+        for i in 0..20 {
+            let key = ResponseCache::cache_key("gpt-4", None, &format!("prompt {i}"));
+            cache.put(&key, "gpt-4", &response, 1000).unwrap();
+        }
+
+        let (count, _, _, _) = cache.stats().unwrap();
+        assert_eq!(count, 20, "no max_weight should impose no weight-based eviction");
+    }
+
+    #[test]
+    fn put_with_ttl_overrides_the_cache_wide_ttl() {
+        // Cache-wide TTL is long, but this entry opts into an immediate
+        // (0-minute) expiry, so it should read back as a miss.
+        let (_tmp, cache) = temp_cache(60);
+        let key = ResponseCache::cache_key("gpt-4", None, "ephemeral");
+
+        cache
+            .put_with_ttl(&key, "gpt-4", "short-lived", 10, Some(0))
+            .unwrap();
+
+        let result = cache.get(&key).unwrap();
+        assert!(result.is_none(), "a 0-minute per-entry TTL should expire immediately");
+    }
+
+    #[test]
+    fn put_with_ttl_can_outlive_the_cache_wide_ttl() {
+        // Cache-wide TTL is 0 (instantly expired), but this entry opts into
+        // a long per-entry TTL, so it should still be readable.
+        let (_tmp, cache) = temp_cache(0);
+        let key = ResponseCache::cache_key("gpt-4", None, "durable");
+
+        cache
+            .put_with_ttl(&key, "gpt-4", "long-lived", 10, Some(60))
+            .unwrap();
+
+        let result = cache.get(&key).unwrap();
+        assert_eq!(result.as_deref(), Some("long-lived"));
+    }
+
+    #[test]
+    fn put_without_ttl_falls_back_to_cache_wide_ttl() {
+        let (_tmp, cache) = temp_cache(0);
+        let key = ResponseCache::cache_key("gpt-4", None, "plain put");
+
+        cache.put(&key, "gpt-4", "response", 10).unwrap();
+
+        let result = cache.get(&key).unwrap();
+        assert!(
+            result.is_none(),
+            "put() with no override should still honor the cache-wide TTL"
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::config::Config;
-use crate::memory::{Memory, MemoryCategory};
+use crate::memory::MemoryCategory;
 use anyhow::{bail, Result};
 use std::collections::HashSet;
 use std::fs;
@@ -16,9 +16,6 @@ struct SourceEntry {
 struct MigrationStats {
     from_sqlite: usize,
     from_markdown: usize,
-    imported: usize,
-    skipped_unchanged: usize,
-    renamed_conflicts: usize,
 }
 
 pub async fn handle_command(command: crate::MigrateCommands, config: &Config) -> Result<()> {
@@ -59,26 +56,46 @@ async fn migrate_openclaw_memory(
         return Ok(());
     }
 
+    println!(
+        "{}OpenClaw migration: {} candidate(s) from {}",
+        if dry_run { "Dry run: " } else { "" },
+        entries.len(),
+        source_workspace.display()
+    );
+    println!("  Target: {}", config.workspace_dir.display());
+    println!("    - from markdown: {}", stats.from_markdown);
+    if stats.from_sqlite > 0 {
+        println!(
+            "    - from sqlite:   {} (skipped -- rusqlite removed)",
+            stats.from_sqlite
+        );
+    }
+
+    let rows: Vec<crate::oracle::memory::MemoryPut> = entries
+        .into_iter()
+        .map(|e| crate::oracle::memory::MemoryPut {
+            key: e.key,
+            content: e.content,
+            category: e.category,
+            session_id: None,
+        })
+        .collect();
+
+    let memory = crate::memory::create_oracle_memory_for_migration()?;
+    let outcome = memory.migrate_upsert(&rows, dry_run).await?;
+
+    println!();
+    println!("  Imported:           {}", outcome.imported);
+    println!("  Overwritten:        {}", outcome.overwritten);
+    println!("  Skipped (unchanged):{}", outcome.skipped_unchanged);
+    println!("  Renamed (conflict): {}", outcome.renamed_conflicts);
+
     if dry_run {
-        println!("Dry run: OpenClaw migration preview");
-        println!("  Source: {}", source_workspace.display());
-        println!("  Target: {}", config.workspace_dir.display());
-        println!("  Candidates: {}", entries.len());
-        println!("    - from markdown: {}", stats.from_markdown);
-        if stats.from_sqlite > 0 {
-            println!("    - from sqlite:   {} (skipped -- rusqlite removed)", stats.from_sqlite);
-        }
         println!();
-        println!("Run without --dry-run to import these entries.");
-        return Ok(());
+        println!("Run without --dry-run to apply these changes.");
     }
 
-    // In the Oracle-only build, migration target must be Oracle.
-    // For now, bail until Oracle memory factory is wired (Task 9).
-    bail!(
-        "Migration target requires Oracle memory backend.\n\
-         Run `zeroclaw setup-oracle` to configure Oracle AI Database, then retry."
-    );
+    Ok(())
 }
 
 fn collect_source_entries(
@@ -224,18 +241,6 @@ fn normalize_key(key: &str, fallback_idx: usize) -> String {
     trimmed.to_string()
 }
 
-#[allow(dead_code)]
-async fn next_available_key(memory: &dyn Memory, base: &str) -> Result<String> {
-    for i in 1..=10_000 {
-        let candidate = format!("{base}__openclaw_{i}");
-        if memory.get(&candidate).await?.is_none() {
-            return Ok(candidate);
-        }
-    }
-
-    bail!("Unable to allocate non-conflicting key for '{base}'")
-}
-
 fn resolve_openclaw_workspace(source: Option<PathBuf>) -> Result<PathBuf> {
     if let Some(src) = source {
         return Ok(src);
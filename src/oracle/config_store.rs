@@ -2,25 +2,57 @@
 //!
 //! Stores configuration key-value pairs in `ZERO_CONFIG`, scoped per agent.
 //! Includes convenience methods for the full agent configuration blob.
+//! Calls go through a [`RetryableConnection`] so a dropped session is
+//! retried rather than failing the caller outright.
+//!
+//! Every [`set`](OracleConfigStore::set) appends the value it's about to
+//! replace to `ZERO_CONFIG_HISTORY` first, so [`history`](OracleConfigStore::history)
+//! can list prior versions and [`revert`](OracleConfigStore::revert) can
+//! restore one. An optional observer, registered with
+//! [`on_change`](OracleConfigStore::on_change), is invoked after each
+//! successful commit — the same after-commit notification shape as the
+//! transaction-observer hooks in embedded databases like SQLite's
+//! `update_hook` or sled's `subscribe`.
 
-use oracle::Connection;
-use std::sync::{Arc, Mutex};
+use crate::oracle::connection::RetryableConnection;
+use std::sync::Mutex;
 use tracing::debug;
 
+/// One historical value of a config key, as returned by
+/// [`OracleConfigStore::history`].
+#[derive(Debug, Clone)]
+pub struct ConfigHistoryEntry {
+    pub old_value: Option<String>,
+    pub changed_at: String,
+}
+
+/// Observer invoked after a successful [`OracleConfigStore::set`] commits,
+/// with `(key, old_value, new_value)`.
+type ChangeObserver = Box<dyn Fn(&str, Option<&str>, &str) + Send + Sync>;
+
 /// Persistent configuration store backed by Oracle Database.
 pub struct OracleConfigStore {
-    conn: Arc<Mutex<Connection>>,
+    conn: RetryableConnection,
     agent_id: String,
+    observer: Mutex<Option<ChangeObserver>>,
 }
 
 impl OracleConfigStore {
-    pub fn new(conn: Arc<Mutex<Connection>>, agent_id: &str) -> Self {
+    pub fn new(conn: RetryableConnection, agent_id: &str) -> Self {
         Self {
             conn,
             agent_id: agent_id.to_string(),
+            observer: Mutex::new(None),
         }
     }
 
+    /// Register a callback invoked after every `set` that actually commits,
+    /// as `(key, old_value, new_value)`. Replaces any previously registered
+    /// observer; pass a no-op closure to silence notifications.
+    pub fn on_change(&self, observer: impl Fn(&str, Option<&str>, &str) + Send + Sync + 'static) {
+        *self.observer.lock().unwrap_or_else(|e| e.into_inner()) = Some(Box::new(observer));
+    }
+
     /// Save the full agent configuration as a single JSON blob.
     pub fn save_config(&self, config_json: &str) -> anyhow::Result<()> {
         self.set("full_config", config_json)
@@ -32,49 +64,106 @@ impl OracleConfigStore {
     }
 
     /// Set a config key-value pair (upsert).
+    ///
+    /// If `key` already has a value, it's appended to `ZERO_CONFIG_HISTORY`
+    /// before being overwritten, so [`history`](Self::history)/[`revert`](Self::revert)
+    /// can see it later. After a successful commit, any observer registered
+    /// via [`on_change`](Self::on_change) is invoked with the old and new values.
     pub fn set(&self, key: &str, value: &str) -> anyhow::Result<()> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        conn.execute(
-            "MERGE INTO ZERO_CONFIG c
-             USING (SELECT :1 AS config_key, :2 AS agent_id FROM DUAL) src
-             ON (c.config_key = src.config_key AND c.agent_id = src.agent_id)
-             WHEN MATCHED THEN UPDATE SET config_value = :3, updated_at = CURRENT_TIMESTAMP
-             WHEN NOT MATCHED THEN INSERT (config_key, agent_id, config_value)
-                VALUES (:4, :5, :6)",
-            &[&key, &self.agent_id, &value, &key, &self.agent_id, &value],
-        )?;
-        conn.commit()?;
+        let old_value = self.get(key)?;
+
+        self.conn.with_retry(|conn| {
+            if let Some(ref old) = old_value {
+                conn.execute(
+                    "INSERT INTO ZERO_CONFIG_HISTORY (config_key, agent_id, old_value)
+                     VALUES (:1, :2, :3)",
+                    &[&key, &self.agent_id, old],
+                )?;
+            }
+            conn.execute(
+                "MERGE INTO ZERO_CONFIG c
+                 USING (SELECT :1 AS config_key, :2 AS agent_id FROM DUAL) src
+                 ON (c.config_key = src.config_key AND c.agent_id = src.agent_id)
+                 WHEN MATCHED THEN UPDATE SET config_value = :3, updated_at = CURRENT_TIMESTAMP
+                 WHEN NOT MATCHED THEN INSERT (config_key, agent_id, config_value)
+                    VALUES (:4, :5, :6)",
+                &[&key, &self.agent_id, &value, &key, &self.agent_id, &value],
+            )?;
+            conn.commit()
+        })?;
         debug!("Config set: '{key}'");
+
+        if let Some(observer) = self.observer.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            observer(key, old_value.as_deref(), value);
+        }
         Ok(())
     }
 
+    /// List prior values of `key`, most recent first.
+    pub fn history(&self, key: &str) -> anyhow::Result<Vec<ConfigHistoryEntry>> {
+        self.conn.with_retry(|conn| {
+            let rows = conn.query(
+                "SELECT old_value, TO_CHAR(changed_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts
+                 FROM ZERO_CONFIG_HISTORY
+                 WHERE config_key = :1 AND agent_id = :2
+                 ORDER BY history_id DESC",
+                &[&key, &self.agent_id],
+            )?;
+            let mut entries = Vec::new();
+            for row_result in rows {
+                let row = row_result?;
+                entries.push(ConfigHistoryEntry {
+                    old_value: row.get::<_, Option<String>>(0)?,
+                    changed_at: row.get::<_, String>(1)?,
+                });
+            }
+            Ok(entries)
+        })
+    }
+
+    /// Restore `key` to the value it held `version` history entries ago
+    /// (`version` 1 is the most recent prior value, matching the order
+    /// `history` returns). Restoring itself goes through `set`, so it is
+    /// recorded as a new history entry rather than rewriting the past.
+    pub fn revert(&self, key: &str, version: usize) -> anyhow::Result<()> {
+        let entries = self.history(key)?;
+        let target = entries
+            .get(version.saturating_sub(1))
+            .ok_or_else(|| anyhow::anyhow!("No history entry {version} for config key '{key}'"))?;
+        let restored = target
+            .old_value
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("History entry {version} for '{key}' has no value to restore"))?;
+        self.set(key, restored)
+    }
+
     /// Get a config value by key. Returns `None` if not found.
     pub fn get(&self, key: &str) -> anyhow::Result<Option<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        match conn.query_row(
-            "SELECT config_value FROM ZERO_CONFIG WHERE config_key = :1 AND agent_id = :2",
-            &[&key, &self.agent_id],
-        ) {
-            Ok(row) => {
-                let value: Option<String> = row.get(0)?;
-                Ok(value)
+        let result = self.conn.with_retry(|conn| {
+            match conn.query_row(
+                "SELECT config_value FROM ZERO_CONFIG WHERE config_key = :1 AND agent_id = :2",
+                &[&key, &self.agent_id],
+            ) {
+                Ok(row) => row.get::<_, Option<String>>(0),
+                Err(ref e) if e.kind() == oracle::ErrorKind::NoDataFound => Ok(None),
+                Err(e) => Err(e),
             }
-            Err(ref e) if e.kind() == oracle::ErrorKind::NoDataFound => Ok(None),
-            Err(e) => Err(anyhow::anyhow!("Failed to get config '{key}': {e}")),
-        }
+        });
+        result.map_err(|e| anyhow::anyhow!("Failed to get config '{key}': {e}"))
     }
 
     /// List all config keys for this agent.
     pub fn list_keys(&self) -> anyhow::Result<Vec<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        let rows = conn.query(
-            "SELECT config_key FROM ZERO_CONFIG WHERE agent_id = :1 ORDER BY config_key",
-            &[&self.agent_id],
-        )?;
-        let mut keys = Vec::new();
-        for row_result in rows {
-            keys.push(row_result?.get::<_, String>(0)?);
-        }
-        Ok(keys)
+        self.conn.with_retry(|conn| {
+            let rows = conn.query(
+                "SELECT config_key FROM ZERO_CONFIG WHERE agent_id = :1 ORDER BY config_key",
+                &[&self.agent_id],
+            )?;
+            let mut keys = Vec::new();
+            for row_result in rows {
+                keys.push(row_result?.get::<_, String>(0)?);
+            }
+            Ok(keys)
+        })
     }
 }
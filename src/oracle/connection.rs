@@ -3,16 +3,369 @@
 //! Supports two connection modes:
 //! - **FreePDB**: Standard host:port/service connection (Oracle Database Free container)
 //! - **ADB**: Autonomous Database with DSN (wallet-less TLS or mTLS with wallet)
+//!
+//! Connections are pooled (see [`ConnectionPool`]): [`OracleConnectionManager::acquire`]
+//! hands out a [`PooledConnection`] guard that validates itself with a fast
+//! `ping()` before being lent out, grows the pool lazily up to `max_size`
+//! under load, and returns the connection to the pool on drop.
+//! [`RetryableConnection`] builds on the same pool for subsystems that issue
+//! one-off blocking calls and want transient Oracle errors (lost session,
+//! connect timeout, resource busy) retried with capped backoff rather than
+//! surfaced on the first dropped session.
 
 use crate::config::OracleConfig;
 use oracle::{Connection, Connector};
-use std::sync::{Arc, Mutex};
-use tracing::{info, warn};
+use std::collections::VecDeque;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Statement-cache size applied when [`StatementCacheSize::Unbounded`] is
+/// requested. The `oracle` crate's session-level cache still needs a concrete
+/// capacity; this is large enough that no realistic hot-path query set (the
+/// state MERGE, the `ZERO_MEMORIES` upsert, the vector KNN query, ...) evicts
+/// under normal use.
+const UNBOUNDED_STMT_CACHE_SIZE: u32 = 1000;
+
+/// Oracle session statement-cache sizing mode.
+///
+/// Wired through [`OracleConfig::statement_cache_size`] and applied to every
+/// connection the manager opens so repeated SQL text (the state MERGE, the
+/// memory upsert, the vector KNN query) keeps its parsed/prepared form alive
+/// across calls instead of being re-parsed every time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementCacheSize {
+    /// Cache as many distinct statement texts as this workload realistically needs.
+    Unbounded,
+    /// Cache up to `n` distinct statement texts per session.
+    Bounded(u32),
+    /// Disable the statement cache entirely (every call re-parses).
+    Disabled,
+}
+
+impl StatementCacheSize {
+    fn as_raw(self) -> u32 {
+        match self {
+            StatementCacheSize::Unbounded => UNBOUNDED_STMT_CACHE_SIZE,
+            StatementCacheSize::Bounded(n) => n,
+            StatementCacheSize::Disabled => 0,
+        }
+    }
+}
+
+/// Starting delay before the first reconnect retry.
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+/// Upper bound the backoff delay doubles towards between retries.
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+/// Maximum number of reconnect attempts before giving up.
+const RECONNECT_MAX_ATTEMPTS: u32 = 5;
+/// How long [`ConnectionPool::acquire`] waits on the condvar for a
+/// connection to be checked back in before re-checking pool state.
+const POOL_ACQUIRE_WAIT: Duration = Duration::from_secs(30);
+
+/// ORA-03113: end-of-file on communication channel (lost connection).
+const ORA_LOST_CONNECTION: i32 = 3113;
+/// ORA-03114: not connected to Oracle.
+const ORA_NOT_CONNECTED: i32 = 3114;
+/// ORA-12170: TNS connect timeout.
+const ORA_CONNECT_TIMEOUT: i32 = 12170;
+/// ORA-00054: resource busy and acquire with NOWAIT specified (or timeout expired).
+const ORA_RESOURCE_BUSY: i32 = 54;
+
+/// `true` if `err` is one of the transient Oracle errors that
+/// [`ConnectionOptions`]-driven retry logic should retry rather than
+/// propagate immediately: a dropped session (ORA-03113/03114), a connect
+/// timeout (ORA-12170), or a busy resource (ORA-00054).
+fn is_transient_oracle_error(err: &oracle::Error) -> bool {
+    matches!(
+        err.db_error().map(|e| e.code()),
+        Some(ORA_LOST_CONNECTION | ORA_NOT_CONNECTED | ORA_CONNECT_TIMEOUT | ORA_RESOURCE_BUSY)
+    )
+}
+
+/// Retry/backoff knobs for [`RetryableConnection::with_retry`], modeled on
+/// the connect-time `ConnectionOptions { busy_timeout, ... }` shape other
+/// Oracle drivers expose.
+///
+/// Wired through [`OracleConfig::retry_max_attempts`] /
+/// [`OracleConfig::retry_busy_timeout_secs`].
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionOptions {
+    /// Attempts made before giving up (the initial try plus retries).
+    pub max_retries: u32,
+    /// Upper bound the retry backoff doubles towards. It is not a total time
+    /// budget: retrying stops after `max_retries` attempts however little
+    /// time has passed.
+    pub busy_timeout: Duration,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            busy_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ConnectionOptions {
+    fn from_config(config: &OracleConfig) -> Self {
+        Self {
+            max_retries: config.retry_max_attempts.max(1),
+            busy_timeout: Duration::from_secs(config.retry_busy_timeout_secs),
+        }
+    }
+}
+
+/// An idle connection sitting in the pool, tagged with the instant it was
+/// checked in so [`ConnectionPool::shrink_idle_locked`] can evict connections
+/// that outlived the idle timeout.
+struct IdleConn {
+    conn: Connection,
+    idle_since: Instant,
+}
+
+/// Shared pool state, guarded by a single mutex plus a condvar for callers
+/// waiting on a free slot once the pool is at `max_size`.
+struct PoolInner {
+    idle: VecDeque<IdleConn>,
+    /// Number of connections currently open, whether idle or checked out.
+    num_open: usize,
+    min_size: usize,
+    max_size: usize,
+    idle_timeout: Duration,
+    /// Lifetime count of connections discarded on a failed `ping()` and
+    /// transparently rebuilt by [`ConnectionPool::acquire`].
+    reconnects: u64,
+}
+
+/// A checkout/checkin connection pool for one Oracle session config.
+///
+/// Cheap to clone -- every clone shares the same underlying pool and
+/// `OracleConfig`. [`OracleConnectionManager`] hands one out via
+/// [`pool`](OracleConnectionManager::pool); [`RetryableConnection`] wraps one
+/// too, so both a transactional caller holding a [`PooledConnection`] across
+/// several statements and a one-off retried call draw from the same budget
+/// of open connections.
+#[derive(Clone)]
+pub struct ConnectionPool {
+    inner: Arc<Mutex<PoolInner>>,
+    cv: Arc<Condvar>,
+    config: OracleConfig,
+}
+
+impl ConnectionPool {
+    fn new(config: OracleConfig, first_conn: Connection) -> Self {
+        let min_size = config.pool_min_size.max(1) as usize;
+        let max_size = config.pool_max_size.max(min_size as u32) as usize;
+        let idle_timeout = Duration::from_secs(config.pool_idle_timeout_secs);
+
+        let pool = Self {
+            inner: Arc::new(Mutex::new(PoolInner {
+                idle: VecDeque::new(),
+                num_open: 0,
+                min_size,
+                max_size,
+                idle_timeout,
+                reconnects: 0,
+            })),
+            cv: Arc::new(Condvar::new()),
+            config,
+        };
+
+        {
+            let mut inner = pool.inner.lock().expect("pool mutex poisoned at init");
+            inner.idle.push_back(IdleConn {
+                conn: first_conn,
+                idle_since: Instant::now(),
+            });
+            inner.num_open = 1;
+        }
+        pool
+    }
+
+    fn connect(config: &OracleConfig) -> anyhow::Result<Connection> {
+        match config.mode.as_str() {
+            "adb" => OracleConnectionManager::connect_adb(config),
+            _ => OracleConnectionManager::connect_freepdb(config),
+        }
+    }
+
+    /// Open extra idle connections up front until `min_size` is reached (one
+    /// slot is already accounted for by the connection opened in `new`), so
+    /// the pool starts warm instead of growing lazily on the first callers.
+    fn prefill(&self) -> anyhow::Result<()> {
+        let to_open = {
+            let inner = self.inner.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+            inner.min_size.saturating_sub(inner.num_open)
+        };
+
+        for _ in 0..to_open {
+            let conn = Self::connect(&self.config)?;
+            let mut inner = self.inner.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+            inner.idle.push_back(IdleConn {
+                conn,
+                idle_since: Instant::now(),
+            });
+            inner.num_open += 1;
+        }
+        Ok(())
+    }
+
+    /// Check out a connection from the pool, returning a guard that
+    /// releases it back to the pool on drop.
+    ///
+    /// Every checkout runs a fast `ping()` liveness probe; a connection
+    /// that fails the probe is discarded (not returned to the caller) and
+    /// transparently rebuilt. The pool grows lazily up to `max_size` under
+    /// load and blocks callers when it is exhausted until a connection is
+    /// checked back in.
+    pub fn acquire(&self) -> anyhow::Result<PooledConnection> {
+        let mut inner = self.inner.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+
+        loop {
+            self.shrink_idle_locked(&mut inner);
+
+            if let Some(idle) = inner.idle.pop_front() {
+                if idle.conn.ping().is_ok() {
+                    return Ok(PooledConnection::new(idle.conn, self.clone()));
+                }
+                // Dead connection: drop it and rebuild, staying under max_size.
+                debug!("Discarding dead pooled connection (failed ping)");
+                inner.num_open = inner.num_open.saturating_sub(1);
+                inner.reconnects += 1;
+                let fresh = Self::connect(&self.config)?;
+                inner.num_open += 1;
+                return Ok(PooledConnection::new(fresh, self.clone()));
+            }
+
+            if inner.num_open < inner.max_size {
+                let fresh = Self::connect(&self.config)?;
+                inner.num_open += 1;
+                return Ok(PooledConnection::new(fresh, self.clone()));
+            }
+
+            // Pool exhausted -- wait for a connection to be checked back in.
+            inner = self
+                .cv
+                .wait_timeout(inner, POOL_ACQUIRE_WAIT)
+                .map_err(|e| anyhow::anyhow!("{e}"))?
+                .0;
+        }
+    }
+
+    /// Drop idle connections that have been sitting unused longer than
+    /// `idle_timeout`, as long as doing so keeps at least `min_size` open.
+    fn shrink_idle_locked(&self, inner: &mut PoolInner) {
+        let idle_timeout = inner.idle_timeout;
+        let min_size = inner.min_size;
+        while inner.num_open > min_size {
+            match inner.idle.front() {
+                Some(front) if front.idle_since.elapsed() > idle_timeout => {
+                    inner.idle.pop_front();
+                    inner.num_open = inner.num_open.saturating_sub(1);
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Return `conn` to the idle queue and wake one waiter, if any.
+    fn check_in(&self, conn: Connection) {
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.idle.push_back(IdleConn {
+                conn,
+                idle_since: Instant::now(),
+            });
+            self.cv.notify_one();
+        } else {
+            warn!("Pool mutex poisoned while checking in connection; connection dropped");
+        }
+    }
+
+    /// Point-in-time snapshot of pool occupancy, for health/metrics endpoints.
+    pub fn stats(&self) -> anyhow::Result<PoolStats> {
+        let inner = self.inner.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        Ok(PoolStats {
+            num_open: inner.num_open,
+            num_idle: inner.idle.len(),
+            min_size: inner.min_size,
+            max_size: inner.max_size,
+            reconnects: inner.reconnects,
+        })
+    }
+}
+
+/// Point-in-time snapshot of a [`ConnectionPool`]'s occupancy.
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStats {
+    /// Connections currently open, whether idle or checked out.
+    pub num_open: usize,
+    /// Connections currently idle in the pool.
+    pub num_idle: usize,
+    pub min_size: usize,
+    pub max_size: usize,
+    /// Lifetime count of connections discarded on a failed `ping()` and
+    /// transparently rebuilt.
+    pub reconnects: u64,
+}
+
+impl PoolStats {
+    /// Connections currently checked out (`num_open - num_idle`).
+    pub fn num_in_use(&self) -> usize {
+        self.num_open.saturating_sub(self.num_idle)
+    }
+}
+
+/// A connection checked out of the pool. Returns to the pool on drop so
+/// the next [`ConnectionPool::acquire`] call can reuse it. Derefs to
+/// [`Connection`], so it slots into any helper written against `&Connection`.
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    pool: ConnectionPool,
+}
+
+impl PooledConnection {
+    fn new(conn: Connection, pool: ConnectionPool) -> Self {
+        Self {
+            conn: Some(conn),
+            pool,
+        }
+    }
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection taken before drop")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection taken before drop")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.pool.check_in(conn);
+        }
+    }
+}
 
 /// Manages Oracle database connections with FreePDB and ADB support.
+///
+/// All callers draw from a shared [`ConnectionPool`] via
+/// [`acquire`](Self::acquire)/[`pool`](Self::pool), or
+/// [`retryable_conn`](Self::retryable_conn) for one-off blocking calls that
+/// want transient Oracle errors retried rather than surfaced immediately.
 pub struct OracleConnectionManager {
     config: OracleConfig,
-    conn: Arc<Mutex<Connection>>,
+    pool: ConnectionPool,
 }
 
 impl OracleConnectionManager {
@@ -33,15 +386,20 @@ impl OracleConnectionManager {
         };
 
         info!("Oracle connection established");
+
+        let pool = ConnectionPool::new(config.clone(), conn);
+        pool.prefill()?;
+
         Ok(Self {
             config: config.clone(),
-            conn: Arc::new(Mutex::new(conn)),
+            pool,
         })
     }
 
     fn connect_freepdb(config: &OracleConfig) -> anyhow::Result<Connection> {
         let connect_string = format!("//{}:{}/{}", config.host, config.port, config.service);
         let conn = Connector::new(&config.user, &config.password, &connect_string).connect()?;
+        conn.set_stmt_cache_size(config.statement_cache_size.as_raw())?;
         Ok(conn)
     }
 
@@ -51,12 +409,64 @@ impl OracleConnectionManager {
             .as_deref()
             .ok_or_else(|| anyhow::anyhow!("ADB mode requires 'dsn' in [oracle] config"))?;
         let conn = Connector::new(&config.user, &config.password, dsn).connect()?;
+        conn.set_stmt_cache_size(config.statement_cache_size.as_raw())?;
         Ok(conn)
     }
 
-    /// Get a shared reference to the connection.
-    pub fn conn(&self) -> Arc<Mutex<Connection>> {
-        self.conn.clone()
+    /// Rebuild a fresh connection using the manager's configured mode.
+    fn reconnect(&self) -> anyhow::Result<Connection> {
+        match self.config.mode.as_str() {
+            "adb" => Self::connect_adb(&self.config),
+            _ => Self::connect_freepdb(&self.config),
+        }
+    }
+
+    /// Rebuild a fresh connection, retrying transient failures with
+    /// exponential backoff (100ms, 200ms, ... capped at
+    /// [`RECONNECT_MAX_BACKOFF`]) up to [`RECONNECT_MAX_ATTEMPTS`] times.
+    fn reconnect_with_backoff(&self) -> anyhow::Result<Connection> {
+        let mut backoff = RECONNECT_INITIAL_BACKOFF;
+        let mut last_err = None;
+
+        for attempt in 1..=RECONNECT_MAX_ATTEMPTS {
+            match self.reconnect() {
+                Ok(conn) => {
+                    if attempt > 1 {
+                        info!("Reconnected on attempt {attempt}/{RECONNECT_MAX_ATTEMPTS}");
+                    }
+                    return Ok(conn);
+                }
+                Err(e) => {
+                    warn!("Reconnect attempt {attempt}/{RECONNECT_MAX_ATTEMPTS} failed: {e}");
+                    last_err = Some(e);
+                    if attempt < RECONNECT_MAX_ATTEMPTS {
+                        std::thread::sleep(backoff);
+                        backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+                    }
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("reconnect failed with no error recorded")))
+    }
+
+    /// Check out a connection from the shared pool. See
+    /// [`ConnectionPool::acquire`].
+    pub fn acquire(&self) -> anyhow::Result<PooledConnection> {
+        self.pool.acquire()
+    }
+
+    /// Get a clone of the shared connection pool, for subsystems
+    /// (`OracleMemory`, `OracleSessionStore`, ...) that hold onto it across
+    /// an `async`/`spawn_blocking` boundary instead of calling
+    /// [`acquire`](Self::acquire) directly on the manager.
+    pub fn pool(&self) -> ConnectionPool {
+        self.pool.clone()
+    }
+
+    /// Point-in-time snapshot of pool occupancy, for health/metrics endpoints.
+    pub fn pool_stats(&self) -> anyhow::Result<PoolStats> {
+        self.pool.stats()
     }
 
     /// Get the agent ID from config.
@@ -74,10 +484,113 @@ impl OracleConnectionManager {
         &self.config
     }
 
-    /// Check if the connection is alive.
+    /// Hand out a [`RetryableConnection`] backed by the shared pool,
+    /// configured from this manager's `retry_max_attempts` /
+    /// `retry_busy_timeout_secs`.
+    ///
+    /// Use this instead of [`acquire`](Self::acquire) for subsystems
+    /// (embedding generation, config storage) that issue individual
+    /// blocking calls and want transient Oracle errors retried rather than
+    /// surfaced to the caller on the first dropped session.
+    pub fn retryable_conn(&self) -> RetryableConnection {
+        RetryableConnection {
+            pool: self.pool.clone(),
+            options: ConnectionOptions::from_config(&self.config),
+        }
+    }
+
+    /// Re-size the statement cache on a freshly-acquired connection at
+    /// runtime. Only affects the connection checked out for this call; new
+    /// connections opened later by the pool pick up the cache size already
+    /// baked into `connect_freepdb`/`connect_adb` from config.
+    pub fn set_statement_cache_size(&self, size: StatementCacheSize) -> anyhow::Result<()> {
+        let guard = self.acquire()?;
+        guard.set_stmt_cache_size(size.as_raw())?;
+        Ok(())
+    }
+
+    /// Check if the pool can produce a healthy connection, transparently
+    /// rebuilding one with [`reconnect_with_backoff`](Self::reconnect_with_backoff)
+    /// if the first attempt can't even open a fresh connection.
+    ///
+    /// `acquire` already pings and discards dead connections on every
+    /// checkout, so a successful acquire is itself a sufficient health
+    /// signal; this only falls back to an explicit reconnect when `acquire`
+    /// fails outright (e.g. the pool couldn't open a replacement at all).
     pub fn ping(&self) -> bool {
-        self.conn
-            .lock()
-            .map_or(false, |conn| conn.ping().is_ok())
+        if self.acquire().is_ok() {
+            return true;
+        }
+
+        warn!("Pool failed to produce a connection on health check; attempting auto-reconnect");
+        match self.reconnect_with_backoff() {
+            Ok(fresh) => {
+                if let Ok(mut inner) = self.pool.inner.lock() {
+                    inner.num_open += 1;
+                }
+                self.pool.check_in(fresh);
+                info!("Connection auto-reconnected after health check failure");
+                true
+            }
+            Err(e) => {
+                warn!("Auto-reconnect failed after health check failure: {e}");
+                false
+            }
+        }
+    }
+}
+
+/// A connection handle that retries transient Oracle errors with capped
+/// exponential backoff, drawing a fresh pooled connection for each attempt
+/// so a dropped session on attempt N doesn't wedge attempt N+1.
+///
+/// Cheap to clone: it shares the same underlying pool as the
+/// [`OracleConnectionManager`] it came from via [`retryable_conn`](OracleConnectionManager::retryable_conn).
+#[derive(Clone)]
+pub struct RetryableConnection {
+    pool: ConnectionPool,
+    options: ConnectionOptions,
+}
+
+impl RetryableConnection {
+    /// Run `op` against a pooled connection, making up to
+    /// `options.max_retries` attempts in all (with capped exponential
+    /// backoff between them) on a transient Oracle error (ORA-03113/03114/12170/00054). Each attempt
+    /// re-acquires from the pool, which pings and discards dead connections
+    /// on checkout; any non-transient error is returned immediately.
+    pub fn with_retry<T>(
+        &self,
+        mut op: impl FnMut(&Connection) -> Result<T, oracle::Error>,
+    ) -> anyhow::Result<T> {
+        let mut backoff = RECONNECT_INITIAL_BACKOFF;
+        let mut last_err = None;
+
+        for attempt in 1..=self.options.max_retries {
+            let guard = self.pool.acquire()?;
+            let result = op(&guard);
+            drop(guard);
+
+            match result {
+                Ok(value) => return Ok(value),
+                Err(e) if is_transient_oracle_error(&e) => {
+                    warn!(
+                        "Transient Oracle error on attempt {attempt}/{}: {e}",
+                        self.options.max_retries
+                    );
+                    last_err = Some(e);
+                    if attempt < self.options.max_retries {
+                        std::thread::sleep(backoff);
+                        backoff = (backoff * 2).min(self.options.busy_timeout);
+                    }
+                }
+                Err(e) => return Err(anyhow::anyhow!(e)),
+            }
+        }
+
+        Err(anyhow::anyhow!(
+            "Oracle operation failed after {} attempts: {}",
+            self.options.max_retries,
+            last_err.expect("loop runs at least once")
+        ))
     }
 }
@@ -0,0 +1,137 @@
+//! In-memory content-addressed cache for Oracle in-database embeddings.
+//!
+//! Keyed by a hash of model name + text, so repeated snippets (common
+//! preambles, recurring daily-note headers, ...) skip the `VECTOR_EMBEDDING`
+//! round trip entirely. Eviction is plain LRU: once over capacity, `put`
+//! scans for and drops the least-recently-accessed entry. `OracleEmbedding`
+//! additionally write-throughs misses to `ZERO_EMBED_CACHE` so the cache
+//! survives restarts.
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Bounded LRU cache of `cache_key -> embedding vector`.
+pub struct EmbeddingCache {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    max_entries: usize,
+}
+
+struct CacheEntry {
+    vector: Vec<f32>,
+    accessed_at: Instant,
+}
+
+impl EmbeddingCache {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            max_entries,
+        }
+    }
+
+    /// Stable key for `model_name` + `text`, stable across process restarts
+    /// so it doubles as the `ZERO_EMBED_CACHE.cache_key` primary key.
+    pub fn cache_key(model_name: &str, text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(model_name.as_bytes());
+        hasher.update(b"|");
+        hasher.update(text.as_bytes());
+        format!("{:064x}", hasher.finalize())
+    }
+
+    pub fn get(&self, key: &str) -> Option<Vec<f32>> {
+        let mut entries = self.entries.lock();
+        let entry = entries.get_mut(key)?;
+        entry.accessed_at = Instant::now();
+        Some(entry.vector.clone())
+    }
+
+    pub fn put(&self, key: String, vector: Vec<f32>) {
+        let mut entries = self.entries.lock();
+        entries.insert(
+            key,
+            CacheEntry {
+                vector,
+                accessed_at: Instant::now(),
+            },
+        );
+
+        while entries.len() > self.max_entries {
+            let lru_key = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.accessed_at)
+                .map(|(k, _)| k.clone());
+            match lru_key {
+                Some(k) => {
+                    entries.remove(&k);
+                }
+                None => break,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_key_deterministic() {
+        let k1 = EmbeddingCache::cache_key("ALL_MINILM_L12_V2", "hello");
+        let k2 = EmbeddingCache::cache_key("ALL_MINILM_L12_V2", "hello");
+        assert_eq!(k1, k2);
+        assert_eq!(k1.len(), 64);
+    }
+
+    #[test]
+    fn cache_key_varies_by_model_and_text() {
+        let k1 = EmbeddingCache::cache_key("model-a", "hello");
+        let k2 = EmbeddingCache::cache_key("model-b", "hello");
+        let k3 = EmbeddingCache::cache_key("model-a", "goodbye");
+        assert_ne!(k1, k2);
+        assert_ne!(k1, k3);
+    }
+
+    #[test]
+    fn put_and_get() {
+        let cache = EmbeddingCache::new(10);
+        let key = EmbeddingCache::cache_key("model", "text");
+        cache.put(key.clone(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(cache.get(&key), Some(vec![1.0, 2.0, 3.0]));
+    }
+
+    #[test]
+    fn miss_returns_none() {
+        let cache = EmbeddingCache::new(10);
+        assert_eq!(cache.get("nonexistent"), None);
+    }
+
+    #[test]
+    fn lru_eviction() {
+        let cache = EmbeddingCache::new(3);
+        for i in 0..5 {
+            cache.put(format!("key{i}"), vec![i as f32]);
+        }
+        assert!(cache.len() <= 3);
+    }
+
+    #[test]
+    fn get_refreshes_recency() {
+        let cache = EmbeddingCache::new(2);
+        cache.put("a".to_string(), vec![1.0]);
+        cache.put("b".to_string(), vec![2.0]);
+        // Touch "a" so it's more recently used than "b".
+        cache.get("a");
+        cache.put("c".to_string(), vec![3.0]);
+        // "b" should be evicted, not "a".
+        assert_eq!(cache.get("a"), Some(vec![1.0]));
+        assert_eq!(cache.get("b"), None);
+    }
+}
@@ -2,43 +2,77 @@
 //!
 //! Generates embeddings in-database using `VECTOR_EMBEDDING()` with an ONNX
 //! model loaded into Oracle AI Vector Search.  The oracle crate is synchronous,
-//! so every DB call is wrapped in `tokio::task::spawn_blocking`.
+//! so every DB call is wrapped in `tokio::task::spawn_blocking`.  Calls go
+//! through a [`RetryableConnection`] so a dropped session during a batch of
+//! embeddings is retried rather than failing the whole request.
+//!
+//! Repeated text (common preambles, recurring daily-note headers, ...) is
+//! served from an in-memory [`EmbeddingCache`], write-through persisted to
+//! `ZERO_EMBED_CACHE` so the cache survives restarts without re-running
+//! `VECTOR_EMBEDDING` for content this agent has already embedded.
 
 use crate::memory::embeddings::EmbeddingProvider;
+use crate::oracle::connection::RetryableConnection;
+use crate::oracle::embed_cache::EmbeddingCache;
+use crate::oracle::vector::{decode_vector_bytes, vec_to_oracle_string, VectorFormat};
 use async_trait::async_trait;
-use oracle::Connection;
-use std::sync::{Arc, Mutex};
 use tracing::{debug, warn};
 
 /// Embedding dimensions produced by the default ALL_MINILM_L12_V2 ONNX model.
 const DEFAULT_DIMENSIONS: usize = 384;
 
+/// In-memory embedding cache entries kept per `OracleEmbedding` instance.
+const DEFAULT_CACHE_ENTRIES: usize = 5_000;
+
 /// Oracle in-database embedding provider backed by ONNX models.
 pub struct OracleEmbedding {
-    conn: Arc<Mutex<Connection>>,
+    conn: RetryableConnection,
     model_name: String,
     dimensions: usize,
+    cache: EmbeddingCache,
+    format: VectorFormat,
 }
 
 impl OracleEmbedding {
     /// Create a new provider.
     ///
-    /// * `conn` — shared connection from `OracleConnectionManager::conn()`
+    /// * `conn` — a retrying connection handle from `OracleConnectionManager::retryable_conn()`
     /// * `model_name` — ONNX model name registered in Oracle (e.g. `ALL_MINILM_L12_V2`)
-    pub fn new(conn: Arc<Mutex<Connection>>, model_name: &str) -> Self {
+    pub fn new(conn: RetryableConnection, model_name: &str) -> Self {
         Self {
             conn,
             model_name: model_name.to_string(),
             dimensions: DEFAULT_DIMENSIONS,
+            cache: EmbeddingCache::new(DEFAULT_CACHE_ENTRIES),
+            format: VectorFormat::default(),
         }
     }
 
     /// Create with explicit dimensions override.
-    pub fn with_dimensions(conn: Arc<Mutex<Connection>>, model_name: &str, dims: usize) -> Self {
+    pub fn with_dimensions(conn: RetryableConnection, model_name: &str, dims: usize) -> Self {
         Self {
             conn,
             model_name: model_name.to_string(),
             dimensions: dims,
+            cache: EmbeddingCache::new(DEFAULT_CACHE_ENTRIES),
+            format: VectorFormat::default(),
+        }
+    }
+
+    /// Create with an explicit `VectorFormat`, e.g. `Int8 { scale }` for a
+    /// model that was registered with an 8-bit quantized output vector.
+    pub fn with_format(
+        conn: RetryableConnection,
+        model_name: &str,
+        dims: usize,
+        format: VectorFormat,
+    ) -> Self {
+        Self {
+            conn,
+            model_name: model_name.to_string(),
+            dimensions: dims,
+            cache: EmbeddingCache::new(DEFAULT_CACHE_ENTRIES),
+            format,
         }
     }
 
@@ -50,27 +84,165 @@ impl OracleEmbedding {
         let model = self.model_name.clone();
 
         tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
-            let sql = "SELECT COUNT(*) FROM USER_MINING_MODELS WHERE MODEL_NAME = :1";
-            let row = guard.query_row_as::<i64>(sql, &[&model])?;
-            Ok(row > 0)
+            conn.with_retry(|guard| {
+                let sql = "SELECT COUNT(*) FROM USER_MINING_MODELS WHERE MODEL_NAME = :1";
+                guard.query_row_as::<i64>(sql, &[&model]).map(|count| count > 0)
+            })
         })
         .await?
     }
 
     /// Generate a single embedding vector from text using the ONNX model.
     ///
-    /// The SQL uses `VECTOR_EMBEDDING(<model> USING :1 AS DATA)` which
-    /// returns the vector as a string like `[0.123, -0.456, ...]`.
-    fn embed_text_sync(conn: &Connection, model_name: &str, text: &str) -> anyhow::Result<Vec<f32>> {
-        // Oracle VECTOR_EMBEDDING returns a vector; we SELECT TO_CHAR to get a parseable string.
-        let sql = format!(
-            "SELECT TO_CHAR(VECTOR_EMBEDDING({model_name} USING :1 AS DATA)) FROM DUAL"
+    /// Tries [`embed_text_native_sync`] first, which selects the raw
+    /// `VECTOR` and decodes its binary representation directly into
+    /// `Vec<f32>` — no decimal round-trip, so no precision loss and no
+    /// `TO_CHAR` formatting overhead. Falls back to the `TO_CHAR` + text-parse
+    /// path on any error (e.g. a driver that can't fetch a `VECTOR` column as
+    /// raw bytes), mirroring the batch-vs-per-row fallback in
+    /// [`embed_batch_sync`](Self::embed_batch_sync).
+    fn embed_text_sync(
+        conn: &RetryableConnection,
+        model_name: &str,
+        text: &str,
+        format: VectorFormat,
+    ) -> anyhow::Result<Vec<f32>> {
+        match Self::embed_text_native_sync(conn, model_name, text, format) {
+            Ok(vector) => Ok(vector),
+            Err(e) => {
+                debug!("Native vector decode failed ({e}), falling back to TO_CHAR parse");
+                let sql = format!(
+                    "SELECT TO_CHAR(VECTOR_EMBEDDING({model_name} USING :1 AS DATA)) FROM DUAL"
+                );
+                let result: String = conn.with_retry(|guard| guard.query_row_as(&sql, &[&text]))?;
+                parse_oracle_vector(&result)
+            }
+        }
+    }
+
+    /// Generate a single embedding vector by selecting the raw `VECTOR`
+    /// result of `VECTOR_EMBEDDING(...)` (no `TO_CHAR`) and decoding its
+    /// bytes per `format`, avoiding the precision loss of a decimal
+    /// round-trip through text.
+    fn embed_text_native_sync(
+        conn: &RetryableConnection,
+        model_name: &str,
+        text: &str,
+        format: VectorFormat,
+    ) -> anyhow::Result<Vec<f32>> {
+        let sql = format!("SELECT VECTOR_EMBEDDING({model_name} USING :1 AS DATA) FROM DUAL");
+        let bytes: Vec<u8> = conn.with_retry(|guard| guard.query_row_as(&sql, &[&text]))?;
+        decode_vector_bytes(&bytes, format)
+    }
+
+    /// Embed `texts` in a single round trip: batch-insert them into the
+    /// `ZERO_EMBED_BATCH` scratch table, then apply `VECTOR_EMBEDDING` to the
+    /// whole table in one `SELECT`, ordered back by `row_idx`. Falls back to
+    /// the caller on any Oracle error (e.g. a driver/server that rejects the
+    /// array bind, or the scratch table not existing on an un-migrated
+    /// schema) so [`embed`](EmbeddingProvider::embed) can retry per-row.
+    fn embed_batch_sync(
+        conn: &RetryableConnection,
+        model_name: &str,
+        texts: &[String],
+    ) -> anyhow::Result<Vec<Vec<f32>>> {
+        let select_sql = format!(
+            "SELECT row_idx, TO_CHAR(VECTOR_EMBEDDING({model_name} USING text_data AS DATA)) \
+             FROM ZERO_EMBED_BATCH ORDER BY row_idx"
         );
-        let result: String = conn.query_row_as(&sql, &[&text])?;
-        parse_oracle_vector(&result)
+
+        let raw: Vec<(i64, String)> = conn.with_retry(|guard| {
+            // `ZERO_EMBED_BATCH` is `ON COMMIT DELETE ROWS` -- rows only
+            // clear on commit, not on a failed statement. Roll back any
+            // error after the INSERT so a failed SELECT/decode doesn't leave
+            // this session's batch rows dangling in an open transaction and
+            // wedge the next call on `pk_zero_embed_batch`.
+            let result = (|| -> Result<Vec<(i64, String)>, oracle::Error> {
+                let mut batch = guard
+                    .batch(
+                        "INSERT INTO ZERO_EMBED_BATCH (row_idx, text_data) VALUES (:1, :2)",
+                        texts.len(),
+                    )
+                    .build()?;
+                for (i, text) in texts.iter().enumerate() {
+                    let idx = i as i64;
+                    batch.append_row(&[&idx, text])?;
+                }
+                batch.execute()?;
+
+                let rows = guard.query(&select_sql, &[])?;
+                let mut out = Vec::with_capacity(texts.len());
+                for row_result in rows {
+                    let row = row_result?;
+                    out.push((row.get::<_, i64>(0)?, row.get::<_, String>(1)?));
+                }
+                Ok(out)
+            })();
+
+            match result {
+                Ok(out) => {
+                    guard.commit()?;
+                    Ok(out)
+                }
+                Err(e) => {
+                    if let Err(rollback_err) = guard.rollback() {
+                        warn!(
+                            "Failed to roll back ZERO_EMBED_BATCH after embed_batch_sync error: {rollback_err}"
+                        );
+                    }
+                    Err(e)
+                }
+            }
+        })?;
+
+        raw.iter().map(|(_, s)| parse_oracle_vector(s)).collect()
+    }
+
+    /// Look up `cache_key` in `ZERO_EMBED_CACHE`. Any failure (including the
+    /// table not existing yet on an un-migrated schema) is treated as a
+    /// cache miss rather than an error, since this is a best-effort
+    /// optimization layered on top of the authoritative `VECTOR_EMBEDDING` call.
+    fn fetch_cached_vector(conn: &RetryableConnection, cache_key: &str) -> Option<Vec<f32>> {
+        let sql = "SELECT TO_CHAR(vector) FROM ZERO_EMBED_CACHE WHERE cache_key = :1";
+        match conn.with_retry(|guard| guard.query_row_as::<String>(sql, &[&cache_key])) {
+            Ok(raw) => match parse_oracle_vector(&raw) {
+                Ok(vector) => Some(vector),
+                Err(e) => {
+                    warn!("ZERO_EMBED_CACHE hit for '{cache_key}' had unparseable vector: {e}");
+                    None
+                }
+            },
+            Err(_) => None,
+        }
+    }
+
+    /// Write `vector` to `ZERO_EMBED_CACHE` under `cache_key`, ignoring the
+    /// insert if a row for this key already exists. Failures (e.g. the table
+    /// not existing yet) are logged and swallowed — a missed write-through
+    /// only costs a future cache miss, not correctness.
+    fn store_cached_vector(
+        conn: &RetryableConnection,
+        cache_key: &str,
+        model_name: &str,
+        vector: &[f32],
+    ) {
+        let sql = "MERGE INTO ZERO_EMBED_CACHE t
+             USING (SELECT :1 AS cache_key FROM DUAL) src
+             ON (t.cache_key = src.cache_key)
+             WHEN NOT MATCHED THEN INSERT (cache_key, model_name, dims, vector)
+                VALUES (:2, :3, :4, TO_VECTOR(:5))";
+        let dims = vector.len() as i64;
+        let vector_str = vec_to_oracle_string(vector);
+        let result = conn.with_retry(|guard| {
+            guard.execute(
+                sql,
+                &[&cache_key, &cache_key, &model_name, &dims, &vector_str],
+            )?;
+            guard.commit()
+        });
+        if let Err(e) = result {
+            warn!("Failed to write-through embedding cache entry '{cache_key}': {e}");
+        }
     }
 }
 
@@ -114,29 +286,84 @@ impl EmbeddingProvider for OracleEmbedding {
             return Ok(Vec::new());
         }
 
-        let conn = self.conn.clone();
-        let model = self.model_name.clone();
-        // Clone texts into owned Strings for the blocking closure
-        let owned_texts: Vec<String> = texts.iter().map(|t| t.to_string()).collect();
+        // Partition into cache hits (served immediately) and misses (need a
+        // DB round trip), preserving original order via `slots`.
+        let keys: Vec<String> = texts
+            .iter()
+            .map(|t| EmbeddingCache::cache_key(&self.model_name, t))
+            .collect();
+        let mut slots: Vec<Option<Vec<f32>>> = Vec::with_capacity(texts.len());
+        let mut miss_indices = Vec::new();
+        for (i, key) in keys.iter().enumerate() {
+            slots.push(self.cache.get(key));
+            if slots[i].is_none() {
+                miss_indices.push(i);
+            }
+        }
 
-        tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
-
-            let mut embeddings = Vec::with_capacity(owned_texts.len());
-            for text in &owned_texts {
-                let vec = Self::embed_text_sync(&guard, &model, text)?;
-                debug!(
-                    "Embedded text ({} chars) -> {} dims",
-                    text.len(),
-                    vec.len()
-                );
-                embeddings.push(vec);
+        if !miss_indices.is_empty() {
+            let conn = self.conn.clone();
+            let model = self.model_name.clone();
+            let format = self.format;
+            let miss_keys: Vec<String> = miss_indices.iter().map(|&i| keys[i].clone()).collect();
+            let miss_texts: Vec<String> =
+                miss_indices.iter().map(|&i| texts[i].to_string()).collect();
+
+            let fetched = tokio::task::spawn_blocking(move || {
+                // Split the in-memory misses further: a persisted-cache hit
+                // needs no embedding call at all; everything else is embedded
+                // in one batched round trip (falling back to per-row on error).
+                let mut out: Vec<Option<Vec<f32>>> = vec![None; miss_texts.len()];
+                let mut need_embed = Vec::new();
+                for (i, key) in miss_keys.iter().enumerate() {
+                    match Self::fetch_cached_vector(&conn, key) {
+                        Some(vector) => {
+                            debug!("Embedding cache hit (persisted): {} chars", miss_texts[i].len());
+                            out[i] = Some(vector);
+                        }
+                        None => need_embed.push(i),
+                    }
+                }
+
+                if !need_embed.is_empty() {
+                    let to_embed: Vec<String> =
+                        need_embed.iter().map(|&i| miss_texts[i].clone()).collect();
+                    let embedded = match Self::embed_batch_sync(&conn, &model, &to_embed) {
+                        Ok(vectors) => vectors,
+                        Err(e) => {
+                            debug!("Batch embedding failed ({e}), falling back to per-row calls");
+                            to_embed
+                                .iter()
+                                .map(|text| Self::embed_text_sync(&conn, &model, text, format))
+                                .collect::<anyhow::Result<Vec<_>>>()?
+                        }
+                    };
+                    for (&i, vector) in need_embed.iter().zip(embedded.into_iter()) {
+                        debug!(
+                            "Embedded text ({} chars) -> {} dims",
+                            miss_texts[i].len(),
+                            vector.len()
+                        );
+                        Self::store_cached_vector(&conn, &miss_keys[i], &model, &vector);
+                        out[i] = Some(vector);
+                    }
+                }
+
+                Ok::<_, anyhow::Error>(
+                    out.into_iter()
+                        .map(|v| v.expect("every miss slot filled"))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .await??;
+
+            for (&i, vector) in miss_indices.iter().zip(fetched.into_iter()) {
+                self.cache.put(keys[i].clone(), vector.clone());
+                slots[i] = Some(vector);
             }
-            Ok(embeddings)
-        })
-        .await?
+        }
+
+        Ok(slots.into_iter().map(|v| v.expect("every slot filled")).collect())
     }
 }
 
@@ -188,4 +415,28 @@ mod tests {
         assert!((v[0] - 0.015).abs() < 1e-6);
         assert!((v[1] - (-30.0)).abs() < f32::EPSILON);
     }
+
+    #[test]
+    fn parse_vector_nan_and_infinity_parse_silently() {
+        // `f32::from_str` accepts "NaN"/"inf" without error, so a malformed
+        // or corrupted `TO_CHAR` result containing these tokens is not
+        // rejected — it silently produces a NaN/infinite embedding element
+        // instead of a parse failure. `embed_text_native_sync`'s byte decode
+        // sidesteps this: it never goes through a textual float parse.
+        let v = parse_oracle_vector("[NaN, inf, -inf]").unwrap();
+        assert_eq!(v.len(), 3);
+        assert!(v[0].is_nan());
+        assert_eq!(v[1], f32::INFINITY);
+        assert_eq!(v[2], f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn parse_vector_denormalized_element_loses_no_extra_precision_but_is_unchecked() {
+        // Subnormal f32 values parse without error or range-checking; there
+        // is no guard against a `TO_CHAR` result that has truncated a value
+        // below f32's normal range into an inexact denormal.
+        let v = parse_oracle_vector("[1.0e-40]").unwrap();
+        assert_eq!(v.len(), 1);
+        assert!(v[0] > 0.0 && v[0] < 1e-38);
+    }
 }
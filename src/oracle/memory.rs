@@ -6,41 +6,1812 @@
 
 use crate::memory::embeddings::EmbeddingProvider;
 use crate::memory::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::oracle::connection::{ConnectionPool, PoolStats};
+use crate::oracle::schema::VectorIndexConfig;
+use crate::oracle::vector::DistanceMetric;
 use crate::oracle::vector::{similarity_from_distance, vec_to_oracle_string};
 use async_trait::async_trait;
 use oracle::Connection;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
+/// Capacity of each [`OracleMemory`]'s [`MemoryEvent`] broadcast channel.
+/// Subscribers that fall this far behind drop the oldest events (a `Lagged`
+/// error on their next `recv`) rather than blocking the store.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// `session_id` tag written on every row `migrate_upsert` creates or
+/// overwrites, so a later migration run can tell "this key is ours to
+/// overwrite" apart from "a user already wrote something at this key".
+const MIGRATION_SESSION_TAG: &str = "openclaw_migration";
+
 /// Minimum similarity score to include in recall results.
 /// Results with distance-based similarity below this are filtered out.
 const MIN_SIMILARITY: f64 = 0.3;
 
-/// Oracle-backed memory store with vector search support.
-pub struct OracleMemory {
-    conn: Arc<Mutex<Connection>>,
-    agent_id: String,
-    embedder: Arc<dyn EmbeddingProvider>,
-}
+/// Reciprocal Rank Fusion constant. Smaller values weight top ranks more
+/// heavily; 60 is the standard default from the original RRF paper.
+const RRF_K: f64 = 60.0;
+
+/// Fuse multiple ranked ID lists with Reciprocal Rank Fusion.
+///
+/// Each list is assumed to already be ordered best-first. Every ID's
+/// contribution from a given list is `1 / (k + rank)` where `rank` is its
+/// 1-based position in that list; an ID's fused score is the sum of its
+/// contributions across all lists it appears in (an ID present in only one
+/// list still gets that list's contribution).
+fn fuse_rrf(lists: &[Vec<String>]) -> HashMap<String, f64> {
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    for list in lists {
+        for (idx, id) in list.iter().enumerate() {
+            let rank = (idx + 1) as f64;
+            *scores.entry(id.clone()).or_insert(0.0) += 1.0 / (RRF_K + rank);
+        }
+    }
+    scores
+}
+
+/// Per-category time-to-live policy for memory entries.
+///
+/// `Core` entries never expire regardless of this policy; the other
+/// variants set how many seconds after the last store of a key an entry
+/// becomes eligible for garbage collection (a `get` does not extend it).
+/// `None` means "never expires".
+#[derive(Debug, Clone, Copy)]
+pub struct TtlPolicy {
+    pub daily_ttl_secs: Option<i64>,
+    pub conversation_ttl_secs: Option<i64>,
+    pub custom_ttl_secs: Option<i64>,
+}
+
+impl Default for TtlPolicy {
+    fn default() -> Self {
+        Self {
+            daily_ttl_secs: Some(30 * 24 * 3600),     // 30 days
+            conversation_ttl_secs: Some(24 * 3600),   // 1 day
+            custom_ttl_secs: None,
+        }
+    }
+}
+
+impl TtlPolicy {
+    /// TTL in seconds for a given category, or `None` if it never expires.
+    fn ttl_for(&self, category: &MemoryCategory) -> Option<i64> {
+        match category {
+            MemoryCategory::Core => None,
+            MemoryCategory::Daily => self.daily_ttl_secs,
+            MemoryCategory::Conversation => self.conversation_ttl_secs,
+            MemoryCategory::Custom(_) => self.custom_ttl_secs,
+        }
+    }
+}
+
+/// One row for [`OracleMemory::put_many`].
+#[derive(Debug, Clone)]
+pub struct MemoryPut {
+    pub key: String,
+    pub content: String,
+    pub category: MemoryCategory,
+    pub session_id: Option<String>,
+}
+
+/// Which ranker(s) [`OracleMemory::recall_with`] consults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecallMode {
+    /// Vector similarity search only.
+    VectorOnly,
+    /// Lexical search only -- Oracle Text `CONTAINS` where the migration-7
+    /// text index exists, falling back to `LIKE` otherwise.
+    Keyword,
+    /// Run both rankers and fuse their ranked lists with Reciprocal Rank
+    /// Fusion. This is what `Memory::recall` uses.
+    Hybrid,
+}
+
+/// Return value of [`OracleMemory::store_returning`]: the canonical row as
+/// stored (with its generated `memory_id` and normalized timestamp), plus
+/// whether the call inserted a new key or updated an existing one -- the
+/// "returning" row a caller would otherwise need a second `get` to see.
+#[derive(Debug, Clone)]
+pub struct StoreResult {
+    pub entry: MemoryEntry,
+    pub was_insert: bool,
+}
+
+/// A mutation (or access) of an agent's memories, broadcast on
+/// [`OracleMemory::subscribe`] after the commit that produced it. Useful for
+/// cache invalidation, summarization triggers, or pushing live updates to a
+/// UI without polling `list`.
+#[derive(Debug, Clone)]
+pub enum MemoryEvent {
+    /// A `store`/`store_returning`/`store_batch` call committed.
+    Stored { entry: MemoryEntry, was_insert: bool },
+    /// A `forget`/`forget_returning` call deleted `key`.
+    Forgotten { key: String },
+    /// A `get` call read `key`.
+    Accessed { key: String },
+}
+
+/// Runtime snapshot of an agent's stored memories.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStats {
+    pub total: usize,
+    pub by_category: HashMap<String, usize>,
+    /// Rows not expired as of this snapshot (`expires_at IS NULL OR
+    /// expires_at > CURRENT_TIMESTAMP`).
+    pub active: usize,
+    /// Rows past `expires_at` but not yet physically deleted (awaiting a
+    /// [`OracleMemory::forget_expired`]/[`OracleMemory::gc`] sweep).
+    pub expired: usize,
+    /// Distinct non-NULL `session_id` values across this agent's memories.
+    pub distinct_sessions: usize,
+    /// Approximate on-disk size of stored content, in bytes
+    /// (`SUM(LENGTHB(content))`; excludes the embedding vector and other
+    /// columns).
+    pub estimated_bytes: usize,
+    /// Wall-clock time of the most recent `store`/`store_impl` call, if one
+    /// has completed since this `OracleMemory` was constructed.
+    pub last_store_latency: Option<Duration>,
+    /// Wall-clock time of the most recent `recall`/`recall_with` call, if
+    /// one has completed since this `OracleMemory` was constructed.
+    pub last_recall_latency: Option<Duration>,
+    /// Snapshot of the underlying connection pool's occupancy (size,
+    /// in-use/idle split, lifetime reconnect count).
+    pub pool: Option<PoolStats>,
+}
+
+/// Tuning knobs for [`OracleMemory::consolidate`]'s frequency-weighted
+/// eviction and merge pass. `Core` entries are never capped, evicted, or
+/// merged regardless of this policy.
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    /// Once an agent's total entry count exceeds this, the `total -
+    /// max_entries` lowest-scoring `Daily`/`Conversation` rows are removed.
+    /// Each merged summary adds one row back, and `Core` rows are never
+    /// removed, so the count can end above the cap.
+    pub max_entries: usize,
+    /// Exponential recency decay rate (per second) applied when scoring
+    /// importance: `score = access_count * exp(-lambda * age_seconds)`,
+    /// where `age_seconds` is measured from `updated_at`. Larger values
+    /// forget stale access history faster.
+    pub lambda: f64,
+    /// Minimum number of low-score `Conversation` rows sharing a
+    /// `session_id` required before they're merged into one summarized
+    /// entry (via the caller's summarizer) instead of being evicted
+    /// outright.
+    pub merge_threshold: usize,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_entries: 10_000,
+            // ~1-week half-life: ln(2) / (7 * 24 * 3600).
+            lambda: 0.000_001_146,
+            merge_threshold: 3,
+        }
+    }
+}
+
+/// Result of one [`OracleMemory::consolidate`] pass.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConsolidationReport {
+    /// Rows deleted outright (not folded into a merged summary).
+    pub evicted: usize,
+    /// New summarized entries written by the merge step.
+    pub merged_into: usize,
+    /// Original rows folded into `merged_into` summaries, then deleted.
+    pub merged_from: usize,
+}
+
+/// Result counts for [`OracleMemory::migrate_upsert`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MigrationOutcome {
+    /// Keys that did not exist yet and were inserted.
+    pub imported: usize,
+    /// Keys that already held byte-identical content; left untouched.
+    pub skipped_unchanged: usize,
+    /// Keys that existed with different content but were tagged as owned by
+    /// a prior migration, so they were overwritten in place.
+    pub overwritten: usize,
+    /// Keys that existed with different content and were NOT migration-owned
+    /// (a real conflict); the entry was written under a fresh key instead.
+    pub renamed_conflicts: usize,
+}
+
+/// Oracle-backed memory store with vector search support.
+pub struct OracleMemory {
+    pool: ConnectionPool,
+    agent_id: String,
+    embedder: Arc<dyn EmbeddingProvider>,
+    ttl_policy: TtlPolicy,
+    vector_index: VectorIndexConfig,
+    events: Arc<broadcast::Sender<MemoryEvent>>,
+    /// Wall-clock time of the most recently completed [`Self::store_impl`]
+    /// call, surfaced via [`Self::stats`].
+    last_store_latency: Arc<Mutex<Option<Duration>>>,
+    /// Wall-clock time of the most recently completed [`Self::recall_with`]
+    /// call, surfaced via [`Self::stats`].
+    last_recall_latency: Arc<Mutex<Option<Duration>>>,
+}
+
+impl OracleMemory {
+    /// Create a new Oracle memory backend.
+    ///
+    /// * `pool` — connection pool from `OracleConnectionManager::pool()`
+    /// * `agent_id` — agent identifier for data isolation
+    /// * `embedder` — embedding provider (typically `OracleEmbedding`)
+    pub fn new(
+        pool: ConnectionPool,
+        agent_id: &str,
+        embedder: Arc<dyn EmbeddingProvider>,
+    ) -> Self {
+        Self::with_ttl_policy(pool, agent_id, embedder, TtlPolicy::default())
+    }
+
+    /// Create a new Oracle memory backend with an explicit TTL policy.
+    pub fn with_ttl_policy(
+        pool: ConnectionPool,
+        agent_id: &str,
+        embedder: Arc<dyn EmbeddingProvider>,
+        ttl_policy: TtlPolicy,
+    ) -> Self {
+        Self::with_vector_index(pool, agent_id, embedder, ttl_policy, VectorIndexConfig::default())
+    }
+
+    /// Create a new Oracle memory backend with an explicit TTL policy and
+    /// vector index configuration. `vector_index.metric` must match the
+    /// `DISTANCE` the schema's vector indexes were created with (see
+    /// `schema::init_schema`), or similarity ranking will be wrong.
+    pub fn with_vector_index(
+        pool: ConnectionPool,
+        agent_id: &str,
+        embedder: Arc<dyn EmbeddingProvider>,
+        ttl_policy: TtlPolicy,
+        vector_index: VectorIndexConfig,
+    ) -> Self {
+        let (events, _rx) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            pool,
+            agent_id: agent_id.to_string(),
+            embedder,
+            ttl_policy,
+            vector_index,
+            events: Arc::new(events),
+            last_store_latency: Arc::new(Mutex::new(None)),
+            last_recall_latency: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Subscribe to this agent's memory mutation events (see
+    /// [`MemoryEvent`]). Each subscriber gets its own receiver with its own
+    /// lag tolerance; sending is non-blocking and silently drops the event
+    /// if there are no subscribers, so this never slows down `store`,
+    /// `forget`, or `get`.
+    pub fn subscribe(&self) -> broadcast::Receiver<MemoryEvent> {
+        self.events.subscribe()
+    }
+
+    /// Bulk-delete expired, non-`Core` entries for this agent.
+    ///
+    /// Entries are given one grace sweep: the first time a sweep finds an
+    /// entry past its `expires_at`, `ref_count` is decremented instead of
+    /// deleting outright (so an entry re-stored between sweeps keeps
+    /// living, since `store` resets `expires_at` and bumps `ref_count` back
+    /// up; `get` changes neither). Only once `ref_count` reaches zero does the
+    /// row get physically deleted. Returns the number of rows deleted.
+    pub async fn forget_expired(&self) -> anyhow::Result<usize> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+            Self::sweep_expired(&guard, &agent_id, None)
+        })
+        .await?
+    }
+
+    /// Garbage-collect expired, non-`Core` entries scoped to one session.
+    pub async fn gc(&self, session_id: &str) -> anyhow::Result<usize> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let session_id = session_id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+            Self::sweep_expired(&guard, &agent_id, Some(&session_id))
+        })
+        .await?
+    }
+
+    /// Evict or merge low-value memories so the store doesn't grow without
+    /// bound even once entries are below their TTL.
+    ///
+    /// Scores every `Daily`/`Conversation` entry as `access_count *
+    /// exp(-lambda * age_seconds)` (recency-decayed access frequency),
+    /// `age_seconds` measured from `updated_at`. If the agent's total entry
+    /// count exceeds `policy.max_entries`, the lowest-scoring rows are
+    /// pulled one cap's-worth over the limit. Among those, any cluster of
+    /// at least `policy.merge_threshold` `Conversation` rows sharing a
+    /// `session_id` is folded into one new summarized entry via
+    /// `summarizer` instead of being deleted outright; everything else
+    /// (smaller clusters, `Daily` rows, or all of it if `summarizer` is
+    /// `None`) is evicted individually. `Core` entries are never scored,
+    /// capped, or touched. Merged entries are stored without a fresh
+    /// embedding -- generating one would mean a second async round trip out
+    /// of this otherwise single-transaction pass -- so they're searchable
+    /// via the keyword ranker until something re-stores and re-embeds them.
+    pub async fn consolidate(
+        &self,
+        policy: &RetentionPolicy,
+        summarizer: Option<Box<dyn Fn(&[MemoryEntry]) -> String + Send + Sync>>,
+    ) -> anyhow::Result<ConsolidationReport> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let policy = *policy;
+        let ttl_secs = self.ttl_policy.ttl_for(&MemoryCategory::Conversation);
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let total: i64 = guard.query_row_as(
+                "SELECT COUNT(*) FROM ZERO_MEMORIES WHERE agent_id = :1",
+                &[&agent_id],
+            )?;
+            let overflow = total - policy.max_entries as i64;
+            if overflow <= 0 {
+                return Ok(ConsolidationReport::default());
+            }
+
+            let sql = "
+                SELECT memory_id, key, content, category,
+                       TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                       session_id,
+                       access_count * EXP(-:1 * (
+                           EXTRACT(DAY FROM (CURRENT_TIMESTAMP - updated_at)) * 86400 +
+                           EXTRACT(HOUR FROM (CURRENT_TIMESTAMP - updated_at)) * 3600 +
+                           EXTRACT(MINUTE FROM (CURRENT_TIMESTAMP - updated_at)) * 60 +
+                           EXTRACT(SECOND FROM (CURRENT_TIMESTAMP - updated_at))
+                       )) AS score
+                FROM ZERO_MEMORIES
+                WHERE agent_id = :2
+                  AND category IN ('daily', 'conversation')
+                ORDER BY score ASC
+                FETCH FIRST :3 ROWS ONLY
+            ";
+            let rows = guard.query(sql, &[&policy.lambda, &agent_id, &overflow])?;
+
+            let mut candidates = Vec::new();
+            for row_result in rows {
+                let row = row_result?;
+                let mut entry = row_to_entry(&row)?;
+                let score: f64 = row.get(6)?;
+                entry.score = Some(score);
+                candidates.push(entry);
+            }
+
+            // Group mergeable candidates (Conversation, with a session_id,
+            // as long as a summarizer was supplied) by session so clusters
+            // big enough to summarize get folded into one entry; everything
+            // else falls through to individual eviction below.
+            let mut by_session: HashMap<String, Vec<MemoryEntry>> = HashMap::new();
+            let mut singles: Vec<MemoryEntry> = Vec::new();
+            for entry in candidates {
+                let mergeable = summarizer.is_some()
+                    && matches!(entry.category, MemoryCategory::Conversation)
+                    && entry.session_id.is_some();
+                if mergeable {
+                    let sid = entry.session_id.clone().unwrap();
+                    by_session.entry(sid).or_default().push(entry);
+                } else {
+                    singles.push(entry);
+                }
+            }
+
+            let mut report = ConsolidationReport::default();
+            for (session_id, group) in by_session {
+                if group.len() >= policy.merge_threshold {
+                    let summary = (summarizer.as_ref().unwrap())(&group);
+                    let memory_id = Uuid::new_v4().to_string();
+                    let key = format!("consolidated:{session_id}:{memory_id}");
+                    Self::store_one_tx(
+                        &guard,
+                        &agent_id,
+                        &key,
+                        &summary,
+                        "conversation",
+                        Some(session_id.as_str()),
+                        None,
+                        &memory_id,
+                        ttl_secs,
+                    )?;
+                    for entry in &group {
+                        guard.execute(
+                            "DELETE FROM ZERO_MEMORIES WHERE memory_id = :1 AND agent_id = :2",
+                            &[&entry.id, &agent_id],
+                        )?;
+                    }
+                    report.merged_into += 1;
+                    report.merged_from += group.len();
+                } else {
+                    singles.extend(group);
+                }
+            }
+
+            for entry in &singles {
+                guard.execute(
+                    "DELETE FROM ZERO_MEMORIES WHERE memory_id = :1 AND agent_id = :2",
+                    &[&entry.id, &agent_id],
+                )?;
+            }
+            report.evicted = singles.len();
+
+            guard.commit()?;
+            if report.evicted > 0 || report.merged_into > 0 {
+                debug!(
+                    "Consolidated memories (agent={agent_id}): evicted {}, merged {} rows into {} summaries",
+                    report.evicted, report.merged_from, report.merged_into
+                );
+            }
+            Ok(report)
+        })
+        .await?
+    }
+
+    /// Runtime snapshot of this agent's stored memories, for metrics/health
+    /// endpoints: total/active/expired counts, a per-category breakdown,
+    /// distinct session count, an estimated content byte size, the most
+    /// recent store/recall latency observed by this `OracleMemory` handle,
+    /// and the underlying connection pool's occupancy.
+    pub async fn stats(&self) -> anyhow::Result<MemoryStats> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let last_store_latency = self
+            .last_store_latency
+            .lock()
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .to_owned();
+        let last_recall_latency = self
+            .last_recall_latency
+            .lock()
+            .map_err(|e| anyhow::anyhow!("{e}"))?
+            .to_owned();
+        let pool_stats = self.pool.stats().ok();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let total: i64 = guard.query_row_as(
+                "SELECT COUNT(*) FROM ZERO_MEMORIES WHERE agent_id = :1",
+                &[&agent_id],
+            )?;
+
+            let active: i64 = guard.query_row_as(
+                "SELECT COUNT(*) FROM ZERO_MEMORIES
+                 WHERE agent_id = :1
+                   AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)",
+                &[&agent_id],
+            )?;
+
+            let distinct_sessions: i64 = guard.query_row_as(
+                "SELECT COUNT(DISTINCT session_id) FROM ZERO_MEMORIES
+                 WHERE agent_id = :1 AND session_id IS NOT NULL",
+                &[&agent_id],
+            )?;
+
+            let estimated_bytes: i64 = guard.query_row_as(
+                "SELECT NVL(SUM(LENGTHB(content)), 0) FROM ZERO_MEMORIES WHERE agent_id = :1",
+                &[&agent_id],
+            )?;
+
+            let mut by_category = HashMap::new();
+            let rows = guard.query(
+                "SELECT category, COUNT(*) FROM ZERO_MEMORIES WHERE agent_id = :1 GROUP BY category",
+                &[&agent_id],
+            )?;
+            for row_result in rows {
+                let row = row_result?;
+                let category: String = row.get(0)?;
+                let count: i64 = row.get(1)?;
+                by_category.insert(category, count as usize);
+            }
+
+            Ok(MemoryStats {
+                total: total as usize,
+                by_category,
+                active: active as usize,
+                expired: (total - active) as usize,
+                distinct_sessions: distinct_sessions as usize,
+                estimated_bytes: estimated_bytes as usize,
+                last_store_latency,
+                last_recall_latency,
+                pool: pool_stats,
+            })
+        })
+        .await?
+    }
+
+    /// Bulk-upsert `entries` using Oracle array binding, chunked into
+    /// `batch_size`-row round trips (one `oracle` batch execute per chunk
+    /// instead of one `execute`+`commit` per row).
+    ///
+    /// Returns one `Result` per input entry, in the same order. A chunk that
+    /// executes cleanly reports every row in it as `Ok`; if the batch as a
+    /// whole fails (e.g. one bad value in an otherwise-good chunk), that
+    /// chunk is retried row-by-row so a single bad entry doesn't sink its
+    /// neighbours, and only the offending row(s) come back `Err`.
+    pub async fn put_many(
+        &self,
+        entries: &[MemoryPut],
+        batch_size: usize,
+    ) -> anyhow::Result<Vec<anyhow::Result<()>>> {
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let batch_size = batch_size.max(1);
+
+        // Embed every row up front so the blocking section below is pure DB work.
+        let contents: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+        let embeddings: Vec<Option<Vec<f32>>> = match self.embedder.embed(&contents).await {
+            Ok(vecs) => vecs.into_iter().map(Some).collect(),
+            Err(e) => {
+                warn!(
+                    "Batch embedding failed, storing {} entries without vectors: {e}",
+                    entries.len()
+                );
+                vec![None; entries.len()]
+            }
+        };
+
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let ttl_policy = self.ttl_policy;
+        let rows: Vec<(MemoryPut, Option<Vec<f32>>)> =
+            entries.iter().cloned().zip(embeddings).collect();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let mut results = Vec::with_capacity(rows.len());
+            for chunk in rows.chunks(batch_size) {
+                match Self::put_batch(&guard, &agent_id, &ttl_policy, chunk) {
+                    Ok(()) => results.extend(chunk.iter().map(|_| Ok(()))),
+                    Err(e) => {
+                        warn!(
+                            "Batch of {} memories failed ({e}), retrying row-by-row",
+                            chunk.len()
+                        );
+                        for row in chunk {
+                            results.push(Self::put_batch(
+                                &guard,
+                                &agent_id,
+                                &ttl_policy,
+                                std::slice::from_ref(row),
+                            ));
+                        }
+                    }
+                }
+            }
+            Ok(results)
+        })
+        .await?
+    }
+
+    /// Execute one chunk of `put_many` as a single Oracle array-bind batch.
+    fn put_batch(
+        conn: &Connection,
+        agent_id: &str,
+        ttl_policy: &TtlPolicy,
+        chunk: &[(MemoryPut, Option<Vec<f32>>)],
+    ) -> anyhow::Result<()> {
+        const EXPIRES_AT_EXPR: &str =
+            "CASE WHEN :ttl IS NULL THEN NULL ELSE CURRENT_TIMESTAMP + NUMTODSINTERVAL(:ttl, 'SECOND') END";
+        let sql = format!(
+            "
+            MERGE INTO ZERO_MEMORIES m
+            USING (SELECT :1 AS key, :2 AS agent_id FROM DUAL) src
+            ON (m.key = src.key AND m.agent_id = src.agent_id)
+            WHEN MATCHED THEN
+                UPDATE SET
+                    m.content    = :3,
+                    m.category   = :4,
+                    m.session_id = :5,
+                    m.embedding  = CASE WHEN :6 IS NULL THEN m.embedding ELSE TO_VECTOR(:6, 384, FLOAT32) END,
+                    m.ref_count  = m.ref_count + 1,
+                    m.expires_at = {expr},
+                    m.updated_at = CURRENT_TIMESTAMP
+            WHEN NOT MATCHED THEN
+                INSERT (memory_id, agent_id, key, content, category, session_id, embedding, ref_count, expires_at, created_at, updated_at)
+                VALUES (:7, :8, :9, :10, :11, :12, CASE WHEN :6 IS NULL THEN NULL ELSE TO_VECTOR(:6, 384, FLOAT32) END, 1, {expr}, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            ",
+            expr = EXPIRES_AT_EXPR.replace(":ttl", ":13")
+        );
+
+        let mut batch = conn.batch(&sql, chunk.len()).build()?;
+        for (put, embedding) in chunk {
+            let memory_id = Uuid::new_v4().to_string();
+            let cat_str = put.category.to_string();
+            let vec_str = embedding.as_ref().map(|v| vec_to_oracle_string(v));
+            let ttl_secs = ttl_policy.ttl_for(&put.category);
+            batch.append_row(&[
+                &put.key,
+                agent_id,
+                &put.content,
+                &cat_str,
+                &put.session_id,
+                &vec_str,
+                &memory_id,
+                agent_id,
+                &put.key,
+                &put.content,
+                &cat_str,
+                &put.session_id,
+                &ttl_secs,
+            ])?;
+        }
+        batch.execute()?;
+        conn.commit()?;
+        Ok(())
+    }
+
+    /// Idempotently import `entries` (e.g. from
+    /// `migration::migrate_openclaw_memory`) in a single transaction:
+    ///
+    /// * a key that doesn't exist yet is inserted (`imported`)
+    /// * a key that already holds byte-identical content is left untouched
+    ///   (`skipped_unchanged`)
+    /// * a key that exists with different content, but was itself written by
+    ///   a prior `migrate_upsert` call, is overwritten in place
+    ///   (`overwritten`) — migration owns that row
+    /// * a key that exists with different content and was NOT migration-owned
+    ///   is a genuine conflict: the entry is written under a fresh key from
+    ///   `next_available_key_tx` instead, leaving the existing row alone
+    ///   (`renamed_conflicts`)
+    ///
+    /// The whole batch is one Oracle transaction: on any error the connection
+    /// is rolled back so a partial failure never leaves a half-imported
+    /// state. With `dry_run` set, no writes happen (the transaction is always
+    /// rolled back) and no embeddings are generated; the returned counts are
+    /// exactly what a real run would produce.
+    pub async fn migrate_upsert(
+        &self,
+        entries: &[MemoryPut],
+        dry_run: bool,
+    ) -> anyhow::Result<MigrationOutcome> {
+        if entries.is_empty() {
+            return Ok(MigrationOutcome::default());
+        }
+
+        let embeddings: Vec<Option<Vec<f32>>> = if dry_run {
+            vec![None; entries.len()]
+        } else {
+            let contents: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+            match self.embedder.embed(&contents).await {
+                Ok(vecs) => vecs.into_iter().map(Some).collect(),
+                Err(e) => {
+                    warn!(
+                        "Migration embedding failed, importing {} entries without vectors: {e}",
+                        entries.len()
+                    );
+                    vec![None; entries.len()]
+                }
+            }
+        };
+
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let ttl_policy = self.ttl_policy;
+        let rows: Vec<(MemoryPut, Option<Vec<f32>>)> =
+            entries.iter().cloned().zip(embeddings).collect();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let result = Self::migrate_upsert_tx(&guard, &agent_id, &ttl_policy, &rows, dry_run);
+
+            if result.is_ok() && !dry_run {
+                guard.commit()?;
+            } else {
+                guard.rollback()?;
+            }
+
+            result
+        })
+        .await?
+    }
+
+    /// Resolve and (unless `dry_run`) apply every row of `migrate_upsert`
+    /// against the currently-held connection, without committing — the
+    /// caller commits or rolls back once, after this returns.
+    fn migrate_upsert_tx(
+        conn: &Connection,
+        agent_id: &str,
+        ttl_policy: &TtlPolicy,
+        rows: &[(MemoryPut, Option<Vec<f32>>)],
+        dry_run: bool,
+    ) -> anyhow::Result<MigrationOutcome> {
+        let mut outcome = MigrationOutcome::default();
+        // A dry run writes nothing, so keys it would have written are tracked
+        // here to classify a key repeated later in `rows` as a real run would.
+        let mut dry_run_written: HashMap<String, String> = HashMap::new();
+
+        for (entry, embedding) in rows {
+            let existing = match conn.query_row(
+                "SELECT content, session_id FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+                &[&entry.key, agent_id],
+            ) {
+                Ok(row) => {
+                    let content: String = row.get(0)?;
+                    let session_id: Option<String> = row.get(1)?;
+                    Some((content, session_id))
+                }
+                Err(oracle::Error::NoDataFound) => None,
+                Err(e) => {
+                    return Err(anyhow::anyhow!(
+                        "Failed to look up migration key '{}': {e}",
+                        entry.key
+                    ))
+                }
+            };
+            let existing = match dry_run_written.get(&entry.key) {
+                Some(content) => Some((content.clone(), Some(MIGRATION_SESSION_TAG.to_string()))),
+                None => existing,
+            };
+
+            match existing {
+                None => {
+                    outcome.imported += 1;
+                    if !dry_run {
+                        Self::migrate_write_row(
+                            conn, agent_id, ttl_policy, &entry.key, entry, embedding,
+                        )?;
+                    } else {
+                        dry_run_written.insert(entry.key.clone(), entry.content.clone());
+                    }
+                }
+                Some((content, _)) if content_signature(&content) == content_signature(&entry.content) => {
+                    outcome.skipped_unchanged += 1;
+                }
+                Some((_, session_id)) if session_id.as_deref() == Some(MIGRATION_SESSION_TAG) => {
+                    outcome.overwritten += 1;
+                    if !dry_run {
+                        Self::migrate_write_row(
+                            conn, agent_id, ttl_policy, &entry.key, entry, embedding,
+                        )?;
+                    } else {
+                        dry_run_written.insert(entry.key.clone(), entry.content.clone());
+                    }
+                }
+                Some(_) => {
+                    outcome.renamed_conflicts += 1;
+                    if !dry_run {
+                        let fresh_key = next_available_key_tx(conn, agent_id, &entry.key)?;
+                        Self::migrate_write_row(
+                            conn, agent_id, ttl_policy, &fresh_key, entry, embedding,
+                        )?;
+                    }
+                }
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Insert-or-overwrite one migration row under `key`, tagging it with
+    /// [`MIGRATION_SESSION_TAG`] so a later migration run recognizes it as
+    /// migration-owned. Deliberately does NOT commit — `migrate_upsert`
+    /// commits the whole batch once, after every row has been applied.
+    fn migrate_write_row(
+        conn: &Connection,
+        agent_id: &str,
+        ttl_policy: &TtlPolicy,
+        key: &str,
+        entry: &MemoryPut,
+        embedding: &Option<Vec<f32>>,
+    ) -> anyhow::Result<()> {
+        const EXPIRES_AT_EXPR: &str =
+            "CASE WHEN :ttl IS NULL THEN NULL ELSE CURRENT_TIMESTAMP + NUMTODSINTERVAL(:ttl, 'SECOND') END";
+        let sql = format!(
+            "
+            MERGE INTO ZERO_MEMORIES m
+            USING (SELECT :1 AS key, :2 AS agent_id FROM DUAL) src
+            ON (m.key = src.key AND m.agent_id = src.agent_id)
+            WHEN MATCHED THEN
+                UPDATE SET
+                    m.content    = :3,
+                    m.category   = :4,
+                    m.session_id = :5,
+                    m.embedding  = CASE WHEN :6 IS NULL THEN m.embedding ELSE TO_VECTOR(:6, 384, FLOAT32) END,
+                    m.ref_count  = m.ref_count + 1,
+                    m.expires_at = {expr},
+                    m.updated_at = CURRENT_TIMESTAMP
+            WHEN NOT MATCHED THEN
+                INSERT (memory_id, agent_id, key, content, category, session_id, embedding, ref_count, expires_at, created_at, updated_at)
+                VALUES (:7, :8, :9, :10, :11, :12, CASE WHEN :6 IS NULL THEN NULL ELSE TO_VECTOR(:6, 384, FLOAT32) END, 1, {expr}, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            ",
+            expr = EXPIRES_AT_EXPR.replace(":ttl", ":13")
+        );
+
+        let memory_id = Uuid::new_v4().to_string();
+        let cat_str = entry.category.to_string();
+        let session_id = Some(MIGRATION_SESSION_TAG.to_string());
+        let vec_str = embedding.as_ref().map(|v| vec_to_oracle_string(v));
+        let ttl_secs = ttl_policy.ttl_for(&entry.category);
+
+        conn.execute(
+            &sql,
+            &[
+                &key,
+                &agent_id,
+                &entry.content,
+                &cat_str,
+                &session_id,
+                &vec_str,
+                &memory_id,
+                &agent_id,
+                &key,
+                &entry.content,
+                &cat_str,
+                &session_id,
+                &ttl_secs,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Shared implementation for `forget_expired`/`gc`: decrement
+    /// `ref_count` on expired rows, then delete the ones that reached zero.
+    fn sweep_expired(
+        conn: &Connection,
+        agent_id: &str,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<usize> {
+        let session_clause = if session_id.is_some() {
+            " AND session_id = :2"
+        } else {
+            ""
+        };
+
+        let decrement_sql = format!(
+            "UPDATE ZERO_MEMORIES
+             SET ref_count = ref_count - 1
+             WHERE agent_id = :1
+               AND category != 'core'
+               AND expires_at IS NOT NULL
+               AND expires_at <= CURRENT_TIMESTAMP
+               AND ref_count > 0{session_clause}"
+        );
+        let delete_sql = format!(
+            "DELETE FROM ZERO_MEMORIES
+             WHERE agent_id = :1
+               AND category != 'core'
+               AND expires_at IS NOT NULL
+               AND expires_at <= CURRENT_TIMESTAMP
+               AND ref_count <= 0{session_clause}"
+        );
+
+        if let Some(sid) = session_id {
+            conn.execute(&decrement_sql, &[&agent_id, &sid])?;
+        } else {
+            conn.execute(&decrement_sql, &[&agent_id])?;
+        }
+
+        let deleted = if let Some(sid) = session_id {
+            conn.execute(&delete_sql, &[&agent_id, &sid])?.row_count()?
+        } else {
+            conn.execute(&delete_sql, &[&agent_id])?.row_count()?
+        };
+
+        conn.commit()?;
+        if deleted > 0 {
+            debug!("Swept {deleted} expired memories (agent={agent_id})");
+        }
+        Ok(deleted as usize)
+    }
+
+    /// Like `Memory::recall`, but lets the caller pick which ranker(s) to
+    /// consult instead of always fusing both (see [`RecallMode`]).
+    /// `Memory::recall` is a thin wrapper over this with `RecallMode::Hybrid`.
+    pub async fn recall_with(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+        mode: RecallMode,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let query_str = query.to_string();
+        let session_id = session_id.map(|s| s.to_string());
+        let limit_i64 = limit as i64;
+        let metric = self.vector_index.metric;
+        let started = Instant::now();
+
+        // Try to generate query embedding, unless the caller only wants
+        // keyword results (no sense paying for an embedding we won't use).
+        let query_embedding = if mode == RecallMode::Keyword {
+            None
+        } else {
+            match self.embedder.embed_one(query).await {
+                Ok(vec) => Some(vec),
+                Err(e) => {
+                    warn!("Query embedding failed, falling back to keyword search: {e}");
+                    None
+                }
+            }
+        };
+
+        let result = tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            Self::recall_one_tx(
+                &guard,
+                &agent_id,
+                &query_str,
+                query_embedding.as_deref(),
+                limit_i64,
+                metric,
+                mode,
+                session_id.as_deref(),
+            )
+        })
+        .await??;
+
+        if let Ok(mut last) = self.last_recall_latency.lock() {
+            *last = Some(started.elapsed());
+        }
+
+        Ok(result)
+    }
+
+    /// Run one query's vector+keyword fan-out and RRF fusion against an
+    /// already-held connection -- the unit of work shared by
+    /// [`Self::recall_with`] (one query, own lock) and
+    /// [`Self::recall_batch`] (many queries, one shared lock).
+    #[allow(clippy::too_many_arguments)]
+    fn recall_one_tx(
+        conn: &Connection,
+        agent_id: &str,
+        query_str: &str,
+        embedding: Option<&[f32]>,
+        limit: i64,
+        metric: DistanceMetric,
+        mode: RecallMode,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        // Fan out: pull a ranked ID list from each ranker plus a shared map
+        // of fully hydrated entries so RRF only has to work with small
+        // string keys.
+        let mut by_id: HashMap<String, MemoryEntry> = HashMap::new();
+        let mut vector_ranked: Vec<String> = Vec::new();
+        let mut keyword_ranked: Vec<String> = Vec::new();
+
+        if mode != RecallMode::Keyword {
+            if let Some(emb) = embedding {
+                let vec_str = vec_to_oracle_string(emb);
+
+                // Vector similarity search
+                let (sql, params): (String, Vec<Box<dyn oracle::sql_type::ToSql>>) =
+                    if let Some(sid) = session_id {
+                        (
+                            format!(
+                                "SELECT memory_id, key, content, category,
+                                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                                    session_id,
+                                    VECTOR_DISTANCE(embedding, TO_VECTOR(:1, 384, FLOAT32), {metric}) AS dist
+                             FROM ZERO_MEMORIES
+                             WHERE agent_id = :2
+                               AND embedding IS NOT NULL
+                               AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+                               AND session_id = :3
+                             ORDER BY dist ASC
+                             FETCH FIRST :4 ROWS ONLY",
+                                metric = metric.as_sql(),
+                            ),
+                            vec![
+                                Box::new(vec_str.clone()),
+                                Box::new(agent_id.to_string()),
+                                Box::new(sid.to_string()),
+                                Box::new(limit),
+                            ],
+                        )
+                    } else {
+                        (
+                            format!(
+                                "SELECT memory_id, key, content, category,
+                                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                                    session_id,
+                                    VECTOR_DISTANCE(embedding, TO_VECTOR(:1, 384, FLOAT32), {metric}) AS dist
+                             FROM ZERO_MEMORIES
+                             WHERE agent_id = :2
+                               AND embedding IS NOT NULL
+                               AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+                             ORDER BY dist ASC
+                             FETCH FIRST :3 ROWS ONLY",
+                                metric = metric.as_sql(),
+                            ),
+                            vec![
+                                Box::new(vec_str.clone()),
+                                Box::new(agent_id.to_string()),
+                                Box::new(limit),
+                            ],
+                        )
+                    };
+
+                // Build parameter references
+                let param_refs: Vec<&dyn oracle::sql_type::ToSql> =
+                    params.iter().map(|p| p.as_ref()).collect();
+
+                let rows = conn.query(&sql, param_refs.as_slice())?;
+                for row_result in rows {
+                    let row = row_result?;
+                    let id: String = row.get(0)?;
+                    let key: String = row.get(1)?;
+                    let content: String = row.get(2)?;
+                    let cat_str: String = row.get(3)?;
+                    let ts: String = row.get(4)?;
+                    let sid: Option<String> = row.get(5)?;
+                    let dist: f64 = row.get(6)?;
+                    let similarity = similarity_from_distance(dist, metric);
+
+                    if similarity < MIN_SIMILARITY {
+                        continue;
+                    }
+
+                    vector_ranked.push(id.clone());
+                    by_id.entry(id.clone()).or_insert(MemoryEntry {
+                        id,
+                        key,
+                        content,
+                        category: parse_category(&cat_str),
+                        timestamp: ts,
+                        session_id: sid,
+                        score: None,
+                    });
+                }
+            }
+        }
+
+        // Lexical ranker: Oracle Text `CONTAINS` where the migration-7 text
+        // index exists (relevance-ranked, natural-language-aware), falling
+        // back to plain `LIKE` otherwise. Always runs in hybrid mode (not
+        // just as a vector-miss fallback) so it can contribute to the fusion.
+        if mode != RecallMode::VectorOnly {
+            let rows = Self::keyword_rows(conn, agent_id, session_id, query_str, limit)?;
+            for entry in rows {
+                keyword_ranked.push(entry.id.clone());
+                by_id.entry(entry.id.clone()).or_insert(entry);
+            }
+
+            if !keyword_ranked.is_empty() {
+                debug!(
+                    "Keyword ranker returned {} results for '{query_str}'",
+                    keyword_ranked.len()
+                );
+            }
+        }
+
+        // Fuse the ranked lists with Reciprocal Rank Fusion and write the
+        // combined score back into each entry. A single-ranker mode just
+        // leaves the other list empty, so this degrades cleanly to a plain
+        // ranked list instead of needing separate code paths.
+        let fused_scores = fuse_rrf(&[vector_ranked, keyword_ranked]);
+        let mut entries: Vec<MemoryEntry> = fused_scores
+            .into_iter()
+            .filter_map(|(id, score)| {
+                by_id.remove(&id).map(|mut entry| {
+                    entry.score = Some(score);
+                    entry
+                })
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            b.score
+                .unwrap_or(0.0)
+                .partial_cmp(&a.score.unwrap_or(0.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        entries.truncate(limit as usize);
+
+        Ok(entries)
+    }
+
+    /// Batch form of [`Self::recall_with`] (always [`RecallMode::Hybrid`]):
+    /// every query in `queries` is embedded in a single
+    /// `EmbeddingProvider::embed` round trip, then each query's vector+
+    /// keyword search runs against one held connection lock instead of
+    /// reacquiring it per query. If the batch embedding call fails
+    /// altogether, every query falls back to keyword-only individually
+    /// rather than failing the whole batch. Returns one result list per
+    /// query, in the same order as `queries`.
+    pub async fn recall_batch(
+        &self,
+        queries: &[&str],
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<Vec<MemoryEntry>>> {
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let embeddings: Vec<Option<Vec<f32>>> = match self.embedder.embed(queries).await {
+            Ok(vecs) => vecs.into_iter().map(Some).collect(),
+            Err(e) => {
+                warn!(
+                    "Batch query embedding failed, falling back to keyword search for all {} queries: {e}",
+                    queries.len()
+                );
+                vec![None; queries.len()]
+            }
+        };
+
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let query_strs: Vec<String> = queries.iter().map(|q| q.to_string()).collect();
+        let session_id = session_id.map(|s| s.to_string());
+        let limit_i64 = limit as i64;
+        let metric = self.vector_index.metric;
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            query_strs
+                .iter()
+                .zip(embeddings)
+                .map(|(query_str, embedding)| {
+                    Self::recall_one_tx(
+                        &guard,
+                        &agent_id,
+                        query_str,
+                        embedding.as_deref(),
+                        limit_i64,
+                        metric,
+                        RecallMode::Hybrid,
+                        session_id.as_deref(),
+                    )
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .await?
+    }
+
+    /// Run the lexical ranker: Oracle Text `CONTAINS` first, falling back to
+    /// `LIKE` if it errors -- e.g. the migration-7 text index doesn't exist
+    /// yet on this database, or the query string trips Oracle Text's query
+    /// grammar.
+    fn keyword_rows(
+        conn: &Connection,
+        agent_id: &str,
+        session_id: Option<&str>,
+        query_str: &str,
+        limit: i64,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        match Self::keyword_rows_contains(conn, agent_id, session_id, query_str, limit) {
+            Ok(rows) => Ok(rows),
+            Err(e) => {
+                debug!("Oracle Text CONTAINS search failed, falling back to LIKE: {e}");
+                Self::keyword_rows_like(conn, agent_id, session_id, query_str, limit)
+            }
+        }
+    }
+
+    /// Oracle Text `CONTAINS` keyword search, ranked by `SCORE(1)`. The
+    /// query is wrapped in `{...}` so it's matched as one literal phrase
+    /// rather than parsed as Oracle Text query-grammar operators.
+    fn keyword_rows_contains(
+        conn: &Connection,
+        agent_id: &str,
+        session_id: Option<&str>,
+        query_str: &str,
+        limit: i64,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let literal_query = format!("{{{query_str}}}");
+
+        let sql = if session_id.is_some() {
+            "SELECT memory_id, key, content, category,
+                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                    session_id
+             FROM ZERO_MEMORIES
+             WHERE agent_id = :1
+               AND CONTAINS(content, :2, 1) > 0
+               AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+               AND session_id = :3
+             ORDER BY SCORE(1) DESC
+             FETCH FIRST :4 ROWS ONLY"
+        } else {
+            "SELECT memory_id, key, content, category,
+                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                    session_id
+             FROM ZERO_MEMORIES
+             WHERE agent_id = :1
+               AND CONTAINS(content, :2, 1) > 0
+               AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+             ORDER BY SCORE(1) DESC
+             FETCH FIRST :3 ROWS ONLY"
+        };
+
+        let rows = if let Some(sid) = session_id {
+            conn.query(sql, &[&agent_id, &literal_query, &sid, &limit])?
+        } else {
+            conn.query(sql, &[&agent_id, &literal_query, &limit])?
+        };
+
+        rows.map(|row_result| row_to_entry(&row_result?)).collect()
+    }
+
+    /// Plain `LIKE` keyword search, ranked by recency -- the fallback when
+    /// Oracle Text `CONTAINS` isn't available.
+    fn keyword_rows_like(
+        conn: &Connection,
+        agent_id: &str,
+        session_id: Option<&str>,
+        query_str: &str,
+        limit: i64,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let like_pattern = format!("%{query_str}%");
+
+        let sql = if session_id.is_some() {
+            "SELECT memory_id, key, content, category,
+                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                    session_id
+             FROM ZERO_MEMORIES
+             WHERE agent_id = :1
+               AND (LOWER(content) LIKE LOWER(:2) OR LOWER(key) LIKE LOWER(:3))
+               AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+               AND session_id = :4
+             ORDER BY updated_at DESC
+             FETCH FIRST :5 ROWS ONLY"
+        } else {
+            "SELECT memory_id, key, content, category,
+                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                    session_id
+             FROM ZERO_MEMORIES
+             WHERE agent_id = :1
+               AND (LOWER(content) LIKE LOWER(:2) OR LOWER(key) LIKE LOWER(:3))
+               AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
+             ORDER BY updated_at DESC
+             FETCH FIRST :4 ROWS ONLY"
+        };
+
+        let rows = if let Some(sid) = session_id {
+            conn.query(
+                sql,
+                &[&agent_id, &like_pattern, &like_pattern, &sid, &limit],
+            )?
+        } else {
+            conn.query(sql, &[&agent_id, &like_pattern, &like_pattern, &limit])?
+        };
+
+        rows.map(|row_result| row_to_entry(&row_result?)).collect()
+    }
+
+    /// Like [`Memory::recall`], but searches `ZERO_MEMORY_HISTORY` as it
+    /// stood at `at` (an `YYYY-MM-DDTHH:MI:SS`-formatted instant) instead of
+    /// the live `ZERO_MEMORIES` table. Since history rows don't carry an
+    /// embedding, this is a keyword-only `LIKE` search over whichever
+    /// revision of each key was open at `at`, ranked by recency.
+    pub async fn recall_as_of(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+        at: &str,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let query_str = query.to_string();
+        let session_id = session_id.map(|s| s.to_string());
+        let at = at.to_string();
+        let limit_i64 = limit as i64;
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let like_pattern = format!("%{query_str}%");
+
+            let sql = if session_id.is_some() {
+                "SELECT memory_id, key, content, category,
+                        TO_CHAR(valid_from, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                        session_id
+                 FROM ZERO_MEMORY_HISTORY
+                 WHERE agent_id = :1
+                   AND (LOWER(content) LIKE LOWER(:2) OR LOWER(key) LIKE LOWER(:3))
+                   AND valid_from <= TO_TIMESTAMP(:4, 'YYYY-MM-DD\"T\"HH24:MI:SS')
+                   AND (valid_to IS NULL OR valid_to > TO_TIMESTAMP(:4, 'YYYY-MM-DD\"T\"HH24:MI:SS'))
+                   AND session_id = :5
+                 ORDER BY valid_from DESC
+                 FETCH FIRST :6 ROWS ONLY"
+            } else {
+                "SELECT memory_id, key, content, category,
+                        TO_CHAR(valid_from, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                        session_id
+                 FROM ZERO_MEMORY_HISTORY
+                 WHERE agent_id = :1
+                   AND (LOWER(content) LIKE LOWER(:2) OR LOWER(key) LIKE LOWER(:3))
+                   AND valid_from <= TO_TIMESTAMP(:4, 'YYYY-MM-DD\"T\"HH24:MI:SS')
+                   AND (valid_to IS NULL OR valid_to > TO_TIMESTAMP(:4, 'YYYY-MM-DD\"T\"HH24:MI:SS'))
+                 ORDER BY valid_from DESC
+                 FETCH FIRST :5 ROWS ONLY"
+            };
+
+            let rows = if let Some(ref sid) = session_id {
+                guard.query(
+                    sql,
+                    &[&agent_id, &like_pattern, &like_pattern, &at, sid, &limit_i64],
+                )?
+            } else {
+                guard.query(sql, &[&agent_id, &like_pattern, &like_pattern, &at, &limit_i64])?
+            };
+
+            rows.map(|row_result| row_to_entry(&row_result?)).collect()
+        })
+        .await?
+    }
+
+    /// Reconstruct the value of `key` as it stood at `at` (an
+    /// `YYYY-MM-DDTHH:MI:SS`-formatted instant), i.e. the revision whose
+    /// `[valid_from, valid_to)` window contains `at`. Returns `None` if
+    /// `key` didn't exist yet, or has since been deleted, at that instant.
+    pub async fn get_as_of(&self, key: &str, at: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let key = key.to_string();
+        let at = at.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let sql = "
+                SELECT memory_id, key, content, category,
+                       TO_CHAR(valid_from, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                       session_id
+                FROM ZERO_MEMORY_HISTORY
+                WHERE key = :1 AND agent_id = :2
+                  AND valid_from <= TO_TIMESTAMP(:3, 'YYYY-MM-DD\"T\"HH24:MI:SS')
+                  AND (valid_to IS NULL OR valid_to > TO_TIMESTAMP(:3, 'YYYY-MM-DD\"T\"HH24:MI:SS'))
+            ";
+
+            match guard.query_row(sql, &[&key, &agent_id, &at]) {
+                Ok(row) => Ok(Some(row_to_entry(&row)?)),
+                Err(oracle::Error::NoDataFound) => Ok(None),
+                Err(e) => Err(anyhow::anyhow!("Failed to get memory '{key}' as of '{at}': {e}")),
+            }
+        })
+        .await?
+    }
+
+    /// List every revision of `key`, newest first, from `ZERO_MEMORY_HISTORY`.
+    pub async fn history(&self, key: &str) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let key = key.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let sql = "
+                SELECT memory_id, key, content, category,
+                       TO_CHAR(valid_from, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                       session_id
+                FROM ZERO_MEMORY_HISTORY
+                WHERE key = :1 AND agent_id = :2
+                ORDER BY valid_from DESC
+            ";
+
+            let rows = guard.query(sql, &[&key, &agent_id])?;
+            rows.map(|row_result| row_to_entry(&row_result?)).collect()
+        })
+        .await?
+    }
+
+    /// Shared implementation behind [`Memory::store`] and
+    /// [`Self::store_returning`]: upserts `key`, appends the revision to
+    /// `ZERO_MEMORY_HISTORY`, then re-selects the canonical row in the same
+    /// transaction so both callers see exactly what was persisted.
+    async fn store_impl(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<StoreResult> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let key = key.to_string();
+        let content = content.to_string();
+        let cat_str = category.to_string();
+        let session_id = session_id.map(|s| s.to_string());
+        let memory_id = Uuid::new_v4().to_string();
+        let ttl_secs = self.ttl_policy.ttl_for(&category);
+        let started = Instant::now();
+
+        // Generate embedding (async, outside spawn_blocking)
+        let embedding = match self.embedder.embed_one(&content).await {
+            Ok(vec) => {
+                debug!("Generated embedding ({} dims) for key '{key}'", vec.len());
+                Some(vec)
+            }
+            Err(e) => {
+                warn!("Embedding generation failed for key '{key}': {e}");
+                None
+            }
+        };
+
+        let result = tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let result = Self::store_one_tx(
+                &guard,
+                &agent_id,
+                &key,
+                &content,
+                &cat_str,
+                session_id.as_deref(),
+                embedding.as_deref(),
+                &memory_id,
+                ttl_secs,
+            )?;
 
-impl OracleMemory {
-    /// Create a new Oracle memory backend.
-    ///
-    /// * `conn` — shared connection from `OracleConnectionManager::conn()`
-    /// * `agent_id` — agent identifier for data isolation
-    /// * `embedder` — embedding provider (typically `OracleEmbedding`)
-    pub fn new(
-        conn: Arc<Mutex<Connection>>,
+            guard.commit()?;
+            debug!("Stored memory '{key}' (agent={agent_id})");
+            Ok(result)
+        })
+        .await??;
+
+        if let Ok(mut last) = self.last_store_latency.lock() {
+            *last = Some(started.elapsed());
+        }
+
+        let _ = self.events.send(MemoryEvent::Stored {
+            entry: result.entry.clone(),
+            was_insert: result.was_insert,
+        });
+
+        Ok(result)
+    }
+
+    /// Upsert one memory and append its history revision against an
+    /// already-held connection, without committing -- the unit of work
+    /// shared by [`Self::store_impl`] (one key, own commit) and
+    /// [`Self::store_batch`] (many keys, one shared commit).
+    #[allow(clippy::too_many_arguments)]
+    fn store_one_tx(
+        conn: &Connection,
         agent_id: &str,
-        embedder: Arc<dyn EmbeddingProvider>,
-    ) -> Self {
-        Self {
-            conn,
-            agent_id: agent_id.to_string(),
-            embedder,
+        key: &str,
+        content: &str,
+        cat_str: &str,
+        session_id: Option<&str>,
+        embedding: Option<&[f32]>,
+        memory_id: &str,
+        ttl_secs: Option<i64>,
+    ) -> anyhow::Result<StoreResult> {
+        let existed: i64 = conn.query_row_as(
+            "SELECT COUNT(*) FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+            &[&key, &agent_id],
+        )?;
+        let was_insert = existed == 0;
+
+        // Shared expires_at expression: NULL forever for ttl_secs = NULL
+        // (Core category), otherwise now + ttl.
+        const EXPIRES_AT_EXPR: &str =
+            "CASE WHEN :ttl IS NULL THEN NULL ELSE CURRENT_TIMESTAMP + NUMTODSINTERVAL(:ttl, 'SECOND') END";
+
+        match embedding {
+            Some(vec) => {
+                let vec_str = vec_to_oracle_string(vec);
+                let sql = format!("
+                    MERGE INTO ZERO_MEMORIES m
+                    USING (SELECT :1 AS key, :2 AS agent_id FROM DUAL) src
+                    ON (m.key = src.key AND m.agent_id = src.agent_id)
+                    WHEN MATCHED THEN
+                        UPDATE SET
+                            m.content    = :3,
+                            m.category   = :4,
+                            m.session_id = :5,
+                            m.embedding  = TO_VECTOR(:6, 384, FLOAT32),
+                            m.ref_count  = m.ref_count + 1,
+                            m.expires_at = {expr},
+                            m.updated_at = CURRENT_TIMESTAMP
+                    WHEN NOT MATCHED THEN
+                        INSERT (memory_id, agent_id, key, content, category, session_id, embedding, ref_count, expires_at, created_at, updated_at)
+                        VALUES (:7, :8, :9, :10, :11, :12, TO_VECTOR(:13, 384, FLOAT32), 1, {expr}, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+                ", expr = EXPIRES_AT_EXPR.replace(":ttl", ":14"));
+                conn.execute(
+                    &sql,
+                    &[
+                        &key,                // :1
+                        &agent_id,           // :2
+                        &content,            // :3
+                        &cat_str,            // :4
+                        &session_id,         // :5
+                        &vec_str,            // :6
+                        &memory_id,          // :7
+                        &agent_id,           // :8
+                        &key,                // :9
+                        &content,            // :10
+                        &cat_str,            // :11
+                        &session_id,         // :12
+                        &vec_str,            // :13
+                        &ttl_secs,           // :14
+                    ],
+                )?;
+            }
+            None => {
+                let sql = format!("
+                    MERGE INTO ZERO_MEMORIES m
+                    USING (SELECT :1 AS key, :2 AS agent_id FROM DUAL) src
+                    ON (m.key = src.key AND m.agent_id = src.agent_id)
+                    WHEN MATCHED THEN
+                        UPDATE SET
+                            m.content    = :3,
+                            m.category   = :4,
+                            m.session_id = :5,
+                            m.ref_count  = m.ref_count + 1,
+                            m.expires_at = {expr},
+                            m.updated_at = CURRENT_TIMESTAMP
+                    WHEN NOT MATCHED THEN
+                        INSERT (memory_id, agent_id, key, content, category, session_id, ref_count, expires_at, created_at, updated_at)
+                        VALUES (:6, :7, :8, :9, :10, :11, 1, {expr}, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+                ", expr = EXPIRES_AT_EXPR.replace(":ttl", ":12"));
+                conn.execute(
+                    &sql,
+                    &[
+                        &key,            // :1
+                        &agent_id,       // :2
+                        &content,        // :3
+                        &cat_str,        // :4
+                        &session_id,     // :5
+                        &memory_id,      // :6
+                        &agent_id,       // :7
+                        &key,            // :8
+                        &content,        // :9
+                        &cat_str,        // :10
+                        &session_id,     // :11
+                        &ttl_secs,       // :12
+                    ],
+                )?;
+            }
+        }
+
+        append_history_revision(conn, agent_id, key, content, cat_str, session_id)?;
+
+        let row = conn.query_row(
+            "SELECT memory_id, key, content, category,
+                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                    session_id
+             FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+            &[&key, &agent_id],
+        )?;
+        let entry = row_to_entry(&row)?;
+
+        Ok(StoreResult { entry, was_insert })
+    }
+
+    /// Batch form of [`Self::store_impl`]: every item's content is embedded
+    /// in a single `EmbeddingProvider::embed` round trip, then all upserts
+    /// run against one held connection and commit once, instead of a
+    /// round trip and a transaction per item. If the batch embedding call
+    /// fails altogether, every item degrades to a keyword-only (no
+    /// embedding) store individually rather than failing the whole batch.
+    /// Returns one [`StoreResult`] per item, in the same order as `items`.
+    pub async fn store_batch(
+        &self,
+        items: &[(&str, &str, MemoryCategory, Option<&str>)],
+    ) -> anyhow::Result<Vec<StoreResult>> {
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let contents: Vec<&str> = items.iter().map(|(_, content, _, _)| *content).collect();
+        let embeddings: Vec<Option<Vec<f32>>> = match self.embedder.embed(&contents).await {
+            Ok(vecs) => vecs.into_iter().map(Some).collect(),
+            Err(e) => {
+                warn!(
+                    "Batch embedding failed, storing all {} items without an embedding: {e}",
+                    items.len()
+                );
+                vec![None; items.len()]
+            }
+        };
+
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let ttl_policy = self.ttl_policy;
+        let rows: Vec<(String, String, String, Option<String>)> = items
+            .iter()
+            .map(|(key, content, category, session_id)| {
+                (
+                    key.to_string(),
+                    content.to_string(),
+                    category.to_string(),
+                    session_id.map(|s| s.to_string()),
+                )
+            })
+            .collect();
+        let ttls: Vec<Option<i64>> = items
+            .iter()
+            .map(|(_, _, category, _)| ttl_policy.ttl_for(category))
+            .collect();
+
+        let results = tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let results = rows
+                .iter()
+                .zip(embeddings)
+                .zip(ttls)
+                .map(|(((key, content, cat_str, session_id), embedding), ttl_secs)| {
+                    let memory_id = Uuid::new_v4().to_string();
+                    Self::store_one_tx(
+                        &guard,
+                        &agent_id,
+                        key,
+                        content,
+                        cat_str,
+                        session_id.as_deref(),
+                        embedding.as_deref(),
+                        &memory_id,
+                        ttl_secs,
+                    )
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            guard.commit()?;
+            debug!("Stored {} memories in batch (agent={agent_id})", results.len());
+            Ok(results)
+        })
+        .await??;
+
+        for result in &results {
+            let _ = self.events.send(MemoryEvent::Stored {
+                entry: result.entry.clone(),
+                was_insert: result.was_insert,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Like [`Memory::store`], but returns the canonical stored row (with
+    /// its generated `memory_id` and normalized timestamp) and whether this
+    /// was an insert or an update of an existing key, saving callers a
+    /// second `get` round trip to see what was actually persisted.
+    pub async fn store_returning(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<StoreResult> {
+        self.store_impl(key, content, category, session_id).await
+    }
+
+    /// Shared implementation behind [`Memory::forget`] and
+    /// [`Self::forget_returning`]: looks up the row before deleting it so
+    /// both callers can tell whether (and what) existed.
+    async fn forget_impl(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let conn = self.pool.clone();
+        let agent_id = self.agent_id.clone();
+        let key_owned = key.to_string();
+
+        let existing = tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
+
+            let existing = match guard.query_row(
+                "SELECT memory_id, key, content, category,
+                        TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
+                        session_id
+                 FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+                &[&key_owned, &agent_id],
+            ) {
+                Ok(row) => Some(row_to_entry(&row)?),
+                Err(oracle::Error::NoDataFound) => None,
+                Err(e) => return Err(anyhow::anyhow!("Failed to look up memory '{key_owned}' before forget: {e}")),
+            };
+
+            if existing.is_some() {
+                guard.execute(
+                    "DELETE FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+                    &[&key_owned, &agent_id],
+                )?;
+                guard.execute(
+                    "UPDATE ZERO_MEMORY_HISTORY
+                     SET valid_to = CURRENT_TIMESTAMP
+                     WHERE agent_id = :1 AND key = :2 AND valid_to IS NULL",
+                    &[&agent_id, &key_owned],
+                )?;
+                guard.commit()?;
+                debug!("Forgot memory '{key_owned}' (agent={agent_id})");
+            }
+
+            Ok(existing)
+        })
+        .await??;
+
+        if existing.is_some() {
+            let _ = self.events.send(MemoryEvent::Forgotten { key: key.to_string() });
+        }
+
+        Ok(existing)
+    }
+
+    /// Like [`Memory::forget`], but returns the deleted row (captured
+    /// before the `DELETE`) instead of just whether one existed.
+    pub async fn forget_returning(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        self.forget_impl(key).await
+    }
+}
+
+// ── Migration helpers ────────────────────────────────────────────
+
+/// Content-addressed signature used by `migrate_upsert` to decide whether an
+/// existing key's content matches an incoming entry, mirroring the SHA-256
+/// cache-key convention in `oracle::embed_cache::EmbeddingCache`.
+fn content_signature(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:064x}", hasher.finalize())
+}
+
+/// Find a key derived from `base` that doesn't exist yet for `agent_id`,
+/// trying `{base}__openclaw_1`, `{base}__openclaw_2`, ... Runs inside
+/// `migrate_upsert`'s transaction, so it sees writes already made earlier in
+/// the same batch.
+fn next_available_key_tx(conn: &Connection, agent_id: &str, base: &str) -> anyhow::Result<String> {
+    for i in 1..=10_000 {
+        let candidate = format!("{base}__openclaw_{i}");
+        let exists: i64 = conn.query_row_as(
+            "SELECT COUNT(*) FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+            &[&candidate, &agent_id],
+        )?;
+        if exists == 0 {
+            return Ok(candidate);
         }
     }
+    anyhow::bail!("Unable to allocate non-conflicting key for '{base}'")
+}
+
+// ── History helpers ─────────────────────────────────────────────
+
+/// Append one revision of `key` to `ZERO_MEMORY_HISTORY`, closing whatever
+/// revision was previously open. Called from `store` inside its own
+/// transaction, so the closed and newly-opened rows share a commit with
+/// the `ZERO_MEMORIES` upsert they describe.
+fn append_history_revision(
+    conn: &Connection,
+    agent_id: &str,
+    key: &str,
+    content: &str,
+    cat_str: &str,
+    session_id: Option<&str>,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE ZERO_MEMORY_HISTORY
+         SET valid_to = CURRENT_TIMESTAMP
+         WHERE agent_id = :1 AND key = :2 AND valid_to IS NULL",
+        &[&agent_id, &key],
+    )?;
+
+    let memory_id: String = conn.query_row_as(
+        "SELECT memory_id FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
+        &[&key, &agent_id],
+    )?;
+
+    conn.execute(
+        "INSERT INTO ZERO_MEMORY_HISTORY
+            (memory_id, agent_id, key, content, category, session_id, valid_from, valid_to)
+         VALUES (:1, :2, :3, :4, :5, :6, CURRENT_TIMESTAMP, NULL)",
+        &[&memory_id, &agent_id, &key, &content, &cat_str, &session_id],
+    )?;
+
+    Ok(())
 }
 
 // ── Category helpers ────────────────────────────────────────────
@@ -92,107 +1863,8 @@ impl Memory for OracleMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
-        let conn = self.conn.clone();
-        let agent_id = self.agent_id.clone();
-        let key = key.to_string();
-        let content = content.to_string();
-        let cat_str = category.to_string();
-        let session_id = session_id.map(|s| s.to_string());
-        let memory_id = Uuid::new_v4().to_string();
-
-        // Generate embedding (async, outside spawn_blocking)
-        let embedding = match self.embedder.embed_one(&content).await {
-            Ok(vec) => {
-                debug!("Generated embedding ({} dims) for key '{key}'", vec.len());
-                Some(vec)
-            }
-            Err(e) => {
-                warn!("Embedding generation failed for key '{key}': {e}");
-                None
-            }
-        };
-
-        tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
-
-            match &embedding {
-                Some(vec) => {
-                    let vec_str = vec_to_oracle_string(vec);
-                    let sql = "
-                        MERGE INTO ZERO_MEMORIES m
-                        USING (SELECT :1 AS key, :2 AS agent_id FROM DUAL) src
-                        ON (m.key = src.key AND m.agent_id = src.agent_id)
-                        WHEN MATCHED THEN
-                            UPDATE SET
-                                m.content    = :3,
-                                m.category   = :4,
-                                m.session_id = :5,
-                                m.embedding  = TO_VECTOR(:6, 384, FLOAT32),
-                                m.updated_at = CURRENT_TIMESTAMP
-                        WHEN NOT MATCHED THEN
-                            INSERT (memory_id, agent_id, key, content, category, session_id, embedding, created_at, updated_at)
-                            VALUES (:7, :8, :9, :10, :11, :12, TO_VECTOR(:13, 384, FLOAT32), CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
-                    ";
-                    guard.execute(
-                        sql,
-                        &[
-                            &key,                // :1
-                            &agent_id,           // :2
-                            &content,            // :3
-                            &cat_str,            // :4
-                            &session_id,         // :5
-                            &vec_str,            // :6
-                            &memory_id,          // :7
-                            &agent_id,           // :8
-                            &key,                // :9
-                            &content,            // :10
-                            &cat_str,            // :11
-                            &session_id,         // :12
-                            &vec_str,            // :13
-                        ],
-                    )?;
-                }
-                None => {
-                    let sql = "
-                        MERGE INTO ZERO_MEMORIES m
-                        USING (SELECT :1 AS key, :2 AS agent_id FROM DUAL) src
-                        ON (m.key = src.key AND m.agent_id = src.agent_id)
-                        WHEN MATCHED THEN
-                            UPDATE SET
-                                m.content    = :3,
-                                m.category   = :4,
-                                m.session_id = :5,
-                                m.updated_at = CURRENT_TIMESTAMP
-                        WHEN NOT MATCHED THEN
-                            INSERT (memory_id, agent_id, key, content, category, session_id, created_at, updated_at)
-                            VALUES (:6, :7, :8, :9, :10, :11, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
-                    ";
-                    guard.execute(
-                        sql,
-                        &[
-                            &key,            // :1
-                            &agent_id,       // :2
-                            &content,        // :3
-                            &cat_str,        // :4
-                            &session_id,     // :5
-                            &memory_id,      // :6
-                            &agent_id,       // :7
-                            &key,            // :8
-                            &content,        // :9
-                            &cat_str,        // :10
-                            &session_id,     // :11
-                        ],
-                    )?;
-                }
-            }
-
-            guard.commit()?;
-            debug!("Stored memory '{key}' (agent={agent_id})");
-            Ok(())
-        })
-        .await?
+        self.store_impl(key, content, category, session_id).await?;
+        Ok(())
     }
 
     async fn recall(
@@ -201,184 +1873,16 @@ impl Memory for OracleMemory {
         limit: usize,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let conn = self.conn.clone();
-        let agent_id = self.agent_id.clone();
-        let query_str = query.to_string();
-        let session_id = session_id.map(|s| s.to_string());
-        let limit_i64 = limit as i64;
-
-        // Try to generate query embedding
-        let query_embedding = match self.embedder.embed_one(query).await {
-            Ok(vec) => Some(vec),
-            Err(e) => {
-                warn!("Query embedding failed, falling back to keyword search: {e}");
-                None
-            }
-        };
-
-        tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
-
-            let mut entries = Vec::new();
-
-            if let Some(ref emb) = query_embedding {
-                let vec_str = vec_to_oracle_string(emb);
-
-                // Vector similarity search
-                let (sql, params): (String, Vec<Box<dyn oracle::sql_type::ToSql>>) =
-                    if let Some(ref sid) = session_id {
-                        (
-                            "SELECT memory_id, key, content, category,
-                                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
-                                    session_id,
-                                    VECTOR_DISTANCE(embedding, TO_VECTOR(:1, 384, FLOAT32), COSINE) AS dist
-                             FROM ZERO_MEMORIES
-                             WHERE agent_id = :2
-                               AND embedding IS NOT NULL
-                               AND session_id = :3
-                             ORDER BY dist ASC
-                             FETCH FIRST :4 ROWS ONLY"
-                                .to_string(),
-                            vec![
-                                Box::new(vec_str.clone()),
-                                Box::new(agent_id.clone()),
-                                Box::new(sid.clone()),
-                                Box::new(limit_i64),
-                            ],
-                        )
-                    } else {
-                        (
-                            "SELECT memory_id, key, content, category,
-                                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
-                                    session_id,
-                                    VECTOR_DISTANCE(embedding, TO_VECTOR(:1, 384, FLOAT32), COSINE) AS dist
-                             FROM ZERO_MEMORIES
-                             WHERE agent_id = :2
-                               AND embedding IS NOT NULL
-                             ORDER BY dist ASC
-                             FETCH FIRST :3 ROWS ONLY"
-                                .to_string(),
-                            vec![
-                                Box::new(vec_str.clone()),
-                                Box::new(agent_id.clone()),
-                                Box::new(limit_i64),
-                            ],
-                        )
-                    };
-
-                // Build parameter references
-                let param_refs: Vec<&dyn oracle::sql_type::ToSql> =
-                    params.iter().map(|p| p.as_ref()).collect();
-
-                let rows = guard.query(&sql, param_refs.as_slice())?;
-                for row_result in rows {
-                    let row = row_result?;
-                    let id: String = row.get(0)?;
-                    let key: String = row.get(1)?;
-                    let content: String = row.get(2)?;
-                    let cat_str: String = row.get(3)?;
-                    let ts: String = row.get(4)?;
-                    let sid: Option<String> = row.get(5)?;
-                    let dist: f64 = row.get(6)?;
-                    let similarity = similarity_from_distance(dist);
-
-                    if similarity < MIN_SIMILARITY {
-                        continue;
-                    }
-
-                    entries.push(MemoryEntry {
-                        id,
-                        key,
-                        content,
-                        category: parse_category(&cat_str),
-                        timestamp: ts,
-                        session_id: sid,
-                        score: Some(similarity),
-                    });
-                }
-            }
-
-            // Fallback: keyword search if no embedding or no results
-            if entries.is_empty() {
-                let like_pattern = format!("%{query_str}%");
-
-                let (sql, params): (String, Vec<Box<dyn oracle::sql_type::ToSql>>) =
-                    if let Some(ref sid) = session_id {
-                        (
-                            "SELECT memory_id, key, content, category,
-                                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
-                                    session_id
-                             FROM ZERO_MEMORIES
-                             WHERE agent_id = :1
-                               AND (LOWER(content) LIKE LOWER(:2) OR LOWER(key) LIKE LOWER(:3))
-                               AND session_id = :4
-                             ORDER BY updated_at DESC
-                             FETCH FIRST :5 ROWS ONLY"
-                                .to_string(),
-                            vec![
-                                Box::new(agent_id.clone()),
-                                Box::new(like_pattern.clone()),
-                                Box::new(like_pattern.clone()),
-                                Box::new(sid.clone()),
-                                Box::new(limit_i64),
-                            ],
-                        )
-                    } else {
-                        (
-                            "SELECT memory_id, key, content, category,
-                                    TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
-                                    session_id
-                             FROM ZERO_MEMORIES
-                             WHERE agent_id = :1
-                               AND (LOWER(content) LIKE LOWER(:2) OR LOWER(key) LIKE LOWER(:3))
-                             ORDER BY updated_at DESC
-                             FETCH FIRST :4 ROWS ONLY"
-                                .to_string(),
-                            vec![
-                                Box::new(agent_id.clone()),
-                                Box::new(like_pattern.clone()),
-                                Box::new(like_pattern.clone()),
-                                Box::new(limit_i64),
-                            ],
-                        )
-                    };
-
-                let param_refs: Vec<&dyn oracle::sql_type::ToSql> =
-                    params.iter().map(|p| p.as_ref()).collect();
-
-                let rows = guard.query(&sql, param_refs.as_slice())?;
-                for row_result in rows {
-                    let row = row_result?;
-                    let mut entry = row_to_entry(&row)?;
-                    // Keyword matches get a nominal score
-                    entry.score = Some(0.5);
-                    entries.push(entry);
-                }
-
-                if !entries.is_empty() {
-                    debug!(
-                        "Keyword fallback returned {} results for '{query_str}'",
-                        entries.len()
-                    );
-                }
-            }
-
-            Ok(entries)
-        })
-        .await?
+        self.recall_with(query, limit, session_id, RecallMode::Hybrid).await
     }
 
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
-        let conn = self.conn.clone();
+        let conn = self.pool.clone();
         let agent_id = self.agent_id.clone();
-        let key = key.to_string();
+        let key_owned = key.to_string();
 
-        tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
+        let result = tokio::task::spawn_blocking(move || {
+            let guard = conn.acquire()?;
 
             let sql = "
                 SELECT memory_id, key, content, category,
@@ -386,9 +1890,10 @@ impl Memory for OracleMemory {
                        session_id
                 FROM ZERO_MEMORIES
                 WHERE key = :1 AND agent_id = :2
+                  AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)
             ";
 
-            let result = guard.query_row(sql, &[&key, &agent_id]);
+            let result = guard.query_row(sql, &[&key_owned, &agent_id]);
             match result {
                 Ok(row) => {
                     let entry = row_to_entry(&row)?;
@@ -396,17 +1901,23 @@ impl Memory for OracleMemory {
                     // Bump access count (best-effort, don't fail the read)
                     let _ = guard.execute(
                         "UPDATE ZERO_MEMORIES SET access_count = access_count + 1 WHERE key = :1 AND agent_id = :2",
-                        &[&key, &agent_id],
+                        &[&key_owned, &agent_id],
                     );
                     let _ = guard.commit();
 
                     Ok(Some(entry))
                 }
                 Err(oracle::Error::NoDataFound) => Ok(None),
-                Err(e) => Err(anyhow::anyhow!("Failed to get memory '{key}': {e}")),
+                Err(e) => Err(anyhow::anyhow!("Failed to get memory '{key_owned}': {e}")),
             }
         })
-        .await?
+        .await??;
+
+        if result.is_some() {
+            let _ = self.events.send(MemoryEvent::Accessed { key: key.to_string() });
+        }
+
+        Ok(result)
     }
 
     async fn list(
@@ -414,15 +1925,13 @@ impl Memory for OracleMemory {
         category: Option<&MemoryCategory>,
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let conn = self.conn.clone();
+        let conn = self.pool.clone();
         let agent_id = self.agent_id.clone();
         let cat_str = category.map(|c| c.to_string());
         let session_id = session_id.map(|s| s.to_string());
 
         tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
+            let guard = conn.acquire()?;
 
             // Build SQL dynamically based on filters
             let mut sql = String::from(
@@ -430,7 +1939,8 @@ impl Memory for OracleMemory {
                         TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS') AS ts,
                         session_id
                  FROM ZERO_MEMORIES
-                 WHERE agent_id = :1",
+                 WHERE agent_id = :1
+                   AND (expires_at IS NULL OR expires_at > CURRENT_TIMESTAMP)",
             );
 
             let mut params: Vec<Box<dyn oracle::sql_type::ToSql>> =
@@ -464,39 +1974,15 @@ impl Memory for OracleMemory {
     }
 
     async fn forget(&self, key: &str) -> anyhow::Result<bool> {
-        let conn = self.conn.clone();
-        let agent_id = self.agent_id.clone();
-        let key = key.to_string();
-
-        tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
-
-            let stmt = guard.execute(
-                "DELETE FROM ZERO_MEMORIES WHERE key = :1 AND agent_id = :2",
-                &[&key, &agent_id],
-            )?;
-
-            let deleted = stmt.row_count()? > 0;
-            guard.commit()?;
-
-            if deleted {
-                debug!("Forgot memory '{key}' (agent={agent_id})");
-            }
-            Ok(deleted)
-        })
-        .await?
+        Ok(self.forget_impl(key).await?.is_some())
     }
 
     async fn count(&self) -> anyhow::Result<usize> {
-        let conn = self.conn.clone();
+        let conn = self.pool.clone();
         let agent_id = self.agent_id.clone();
 
         tokio::task::spawn_blocking(move || {
-            let guard = conn
-                .lock()
-                .map_err(|e| anyhow::anyhow!("Connection lock poisoned: {e}"))?;
+            let guard = conn.acquire()?;
 
             let count: i64 = guard.query_row_as(
                 "SELECT COUNT(*) FROM ZERO_MEMORIES WHERE agent_id = :1",
@@ -509,13 +1995,11 @@ impl Memory for OracleMemory {
     }
 
     async fn health_check(&self) -> bool {
-        let conn = self.conn.clone();
+        let conn = self.pool.clone();
 
-        tokio::task::spawn_blocking(move || {
-            conn.lock().map_or(false, |guard| guard.ping().is_ok())
-        })
-        .await
-        .unwrap_or(false)
+        tokio::task::spawn_blocking(move || conn.acquire().is_ok())
+            .await
+            .unwrap_or(false)
     }
 }
 
@@ -555,4 +2039,34 @@ mod tests {
         assert!(MIN_SIMILARITY > 0.0);
         assert!(MIN_SIMILARITY < 1.0);
     }
+
+    #[test]
+    fn fuse_rrf_sums_contributions_from_both_lists() {
+        let vector_list = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let keyword_list = vec!["b".to_string(), "a".to_string()];
+
+        let scores = fuse_rrf(&[vector_list, keyword_list]);
+
+        let expected_a = 1.0 / (RRF_K + 1.0) + 1.0 / (RRF_K + 2.0);
+        let expected_b = 1.0 / (RRF_K + 2.0) + 1.0 / (RRF_K + 1.0);
+        let expected_c = 1.0 / (RRF_K + 3.0);
+
+        assert!((scores["a"] - expected_a).abs() < f64::EPSILON);
+        assert!((scores["b"] - expected_b).abs() < f64::EPSILON);
+        assert!((scores["c"] - expected_c).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn fuse_rrf_only_in_one_list_still_contributes() {
+        let scores = fuse_rrf(&[vec!["solo".to_string()], vec![]]);
+        assert!((scores["solo"] - 1.0 / (RRF_K + 1.0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn fuse_rrf_top_rank_scores_highest() {
+        let list = vec!["first".to_string(), "second".to_string(), "third".to_string()];
+        let scores = fuse_rrf(&[list]);
+        assert!(scores["first"] > scores["second"]);
+        assert!(scores["second"] > scores["third"]);
+    }
 }
@@ -1,17 +1,20 @@
 pub mod connection;
 pub mod schema;
+pub mod embed_cache;
 pub mod embedding;
 pub mod memory;
 pub mod session;
 pub mod state;
 pub mod config_store;
 pub mod prompt;
+pub mod response_cache_store;
 pub mod vector;
 
-pub use connection::OracleConnectionManager;
+pub use connection::{ConnectionPool, OracleConnectionManager, PoolStats, PooledConnection};
 pub use embedding::OracleEmbedding;
-pub use memory::OracleMemory;
+pub use memory::{OracleMemory, RecallMode, StoreResult};
 pub use session::OracleSessionStore;
 pub use state::OracleStateStore;
 pub use config_store::OracleConfigStore;
 pub use prompt::OraclePromptStore;
+pub use response_cache_store::OracleResponseCacheStore;
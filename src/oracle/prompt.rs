@@ -3,29 +3,240 @@
 //! Persists named prompt templates in `ZERO_PROMPTS`, scoped per agent.
 //! Includes `seed_from_workspace` to bootstrap prompts from workspace
 //! markdown files (IDENTITY.md, SOUL.md, USER.md, AGENT.md, AGENTS.md).
+//!
+//! Prompt content may carry a leading YAML frontmatter block (delimited by
+//! `---` fences) describing [`PromptMetadata`] -- title, version, author,
+//! languages. `set_prompt` parses it out via [`parse_frontmatter`] and
+//! persists the fields into their own `ZERO_PROMPTS` columns instead of
+//! leaving them baked into `content`.
+//!
+//! Per-language variants of the same logical prompt are stored as separate
+//! rows named `"{name}@{lang}"` (e.g. `"IDENTITY@rust"`, `"IDENTITY@*"` for
+//! the wildcard fallback) -- `set_prompt` has no special handling for this,
+//! callers just pick the variant name when storing. [`OraclePromptStore::get_prompt_for_language`]
+//! is the resolver that knows the convention.
+//!
+//! The `languages` frontmatter column is *not* this resolver's lookup key --
+//! `ZERO_PROMPTS`'s primary key is `(prompt_name, agent_id)`, so one row can
+//! only ever record one `languages` value and can't hold multiple
+//! per-language bodies at once. The `@lang` row-naming convention above is
+//! what actually lets a prompt have several stored variants; `languages`
+//! remains metadata (e.g. for tooling that lists which prompts a revision
+//! declares support for) rather than a selector.
 
-use oracle::Connection;
+use crate::oracle::connection::ConnectionPool;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
 use std::path::Path;
-use std::sync::{Arc, Mutex};
 use tracing::{debug, info, warn};
 
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Separator between a logical prompt name and its language tag in
+/// per-language variant storage, e.g. `"IDENTITY@rust"`. See
+/// [`OraclePromptStore::get_prompt_for_language`].
+const LANGUAGE_VARIANT_SEP: &str = "@";
+
+/// Marks the start of one prompt's section in an [`OraclePromptStore::export_bundle`]
+/// document; the prompt name follows, terminated by `" -->"`.
+const BUNDLE_HEADER_PREFIX: &str = "<!-- ZEROORACLAW_PROMPT: ";
+const BUNDLE_HEADER_SUFFIX: &str = " -->";
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(raw, TIMESTAMP_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).single()
+}
+
+/// Split an [`OraclePromptStore::export_bundle`]-format document back into
+/// `(name, content)` sections on its `<!-- ZEROORACLAW_PROMPT: ... -->`
+/// header lines. Content before the first header line is discarded.
+fn split_bundle_sections(bundle: &str) -> Vec<(String, String)> {
+    let mut sections = Vec::new();
+    let mut current_name: Option<String> = None;
+    let mut current_content = String::new();
+
+    for line in bundle.lines() {
+        if let Some(name) = line
+            .strip_prefix(BUNDLE_HEADER_PREFIX)
+            .and_then(|rest| rest.strip_suffix(BUNDLE_HEADER_SUFFIX))
+        {
+            if let Some(prev_name) = current_name.take() {
+                sections.push((prev_name, std::mem::take(&mut current_content)));
+            }
+            current_name = Some(name.to_string());
+            continue;
+        }
+        if current_name.is_some() {
+            current_content.push_str(line);
+            current_content.push('\n');
+        }
+    }
+    if let Some(prev_name) = current_name.take() {
+        sections.push((prev_name, current_content));
+    }
+
+    sections
+}
+
+/// Structured metadata parsed from a prompt's leading YAML frontmatter.
+/// Missing fields (or a prompt with no frontmatter at all) fall back to
+/// [`PromptMetadata::default_for`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PromptMetadata {
+    pub title: String,
+    pub version: String,
+    pub author: String,
+    pub languages: Vec<String>,
+}
+
+impl PromptMetadata {
+    /// Defaults for a prompt named `name` with no (or unparseable)
+    /// frontmatter: `title = name`, `version = "1.0"`, `author = "No Author"`,
+    /// `languages = ["*"]`.
+    fn default_for(name: &str) -> Self {
+        Self {
+            title: name.to_string(),
+            version: "1.0".to_string(),
+            author: "No Author".to_string(),
+            languages: vec!["*".to_string()],
+        }
+    }
+}
+
+/// Deserialization target for the raw YAML frontmatter block -- every field
+/// is optional so a partially-specified header (e.g. just `title:`) still
+/// parses, with the rest filled in from [`PromptMetadata::default_for`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawFrontmatter {
+    title: Option<String>,
+    version: Option<String>,
+    author: Option<String>,
+    languages: Option<Vec<String>>,
+}
+
+/// Split `raw` into (metadata, body) on a leading `---` ... `---` YAML
+/// frontmatter fence. Anything without that leading fence, or whose block
+/// fails to parse as YAML, falls back to [`PromptMetadata::default_for`]
+/// with `raw` kept whole as the body -- a malformed header must never lose
+/// the prompt body, only its metadata.
+fn parse_frontmatter(name: &str, raw: &str) -> (PromptMetadata, String) {
+    let lines: Vec<&str> = raw.lines().collect();
+    if lines.first().map(|l| l.trim()) != Some("---") {
+        return (PromptMetadata::default_for(name), raw.trim().to_string());
+    }
+
+    let Some(close_offset) = lines.iter().skip(1).position(|l| l.trim() == "---") else {
+        return (PromptMetadata::default_for(name), raw.trim().to_string());
+    };
+    let close_idx = close_offset + 1;
+    let yaml_block = lines[1..close_idx].join("\n");
+    let body = lines[(close_idx + 1)..].join("\n");
+
+    match serde_yaml::from_str::<RawFrontmatter>(&yaml_block) {
+        Ok(raw_meta) => {
+            let defaults = PromptMetadata::default_for(name);
+            let metadata = PromptMetadata {
+                title: raw_meta.title.unwrap_or(defaults.title),
+                version: raw_meta.version.unwrap_or(defaults.version),
+                author: raw_meta.author.unwrap_or(defaults.author),
+                languages: raw_meta.languages.unwrap_or(defaults.languages),
+            };
+            (metadata, body.trim().to_string())
+        }
+        Err(e) => {
+            warn!("Malformed YAML frontmatter for prompt '{name}', using defaults: {e}");
+            (PromptMetadata::default_for(name), raw.trim().to_string())
+        }
+    }
+}
+
+/// Which field of a prompt a [`PromptMatch`] matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptMatchField {
+    Name,
+    Title,
+}
+
+/// One ranked result from [`OraclePromptStore::search_prompts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptMatch {
+    pub name: String,
+    pub field: PromptMatchField,
+    pub matched_text: String,
+    pub score: i64,
+    pub matched_indices: Vec<usize>,
+}
+
+/// Self-contained fuzzy subsequence scorer. Walks `query`'s characters
+/// left-to-right requiring them to appear in order in `candidate`
+/// (case-insensitively); returns `None` if any query char isn't found.
+/// Awards one base point per matched char, plus bonuses for consecutive
+/// matches, matches at word boundaries (right after `_`, `-`, space, or at
+/// index 0), and case-exact matches. Returns the char indices of every
+/// match for highlighting.
+fn fuzzy_match(query: &str, candidate: &str) -> Option<(i64, Vec<usize>)> {
+    let query_chars: Vec<char> = query.chars().collect();
+    if query_chars.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+
+    let mut score: i64 = 0;
+    let mut indices = Vec::with_capacity(query_chars.len());
+    let mut qi = 0;
+    let mut last_matched: Option<usize> = None;
+
+    for (ci, &c) in candidate_chars.iter().enumerate() {
+        if qi >= query_chars.len() {
+            break;
+        }
+        let q = query_chars[qi];
+        if c.to_ascii_lowercase() != q.to_ascii_lowercase() {
+            continue;
+        }
+
+        score += 1;
+        if c == q {
+            score += 1;
+        }
+        if ci == 0 || matches!(candidate_chars[ci - 1], '_' | '-' | ' ') {
+            score += 2;
+        }
+        if last_matched == Some(ci.saturating_sub(1)) && ci > 0 {
+            score += 3;
+        }
+
+        indices.push(ci);
+        last_matched = Some(ci);
+        qi += 1;
+    }
+
+    if qi < query_chars.len() {
+        None
+    } else {
+        Some((score, indices))
+    }
+}
+
 /// Persistent prompt store backed by Oracle Database.
 pub struct OraclePromptStore {
-    conn: Arc<Mutex<Connection>>,
+    pool: ConnectionPool,
     agent_id: String,
 }
 
 impl OraclePromptStore {
-    pub fn new(conn: Arc<Mutex<Connection>>, agent_id: &str) -> Self {
+    pub fn new(pool: ConnectionPool, agent_id: &str) -> Self {
         Self {
-            conn,
+            pool,
             agent_id: agent_id.to_string(),
         }
     }
 
-    /// Get a prompt by name. Returns `None` if not found.
+    /// Get a prompt's body by name. Returns `None` if not found. Use
+    /// [`Self::get_prompt_with_metadata`] to also get its parsed frontmatter.
     pub fn get_prompt(&self, name: &str) -> anyhow::Result<Option<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         match conn.query_row(
             "SELECT content FROM ZERO_PROMPTS WHERE prompt_name = :1 AND agent_id = :2",
             &[&name, &self.agent_id],
@@ -39,33 +250,233 @@ impl OraclePromptStore {
         }
     }
 
-    /// Set a prompt by name (upsert).
+    /// Get a prompt's parsed [`PromptMetadata`] alongside its body. Returns
+    /// `None` if not found.
+    pub fn get_prompt_with_metadata(
+        &self,
+        name: &str,
+    ) -> anyhow::Result<Option<(PromptMetadata, String)>> {
+        let conn = self.pool.acquire()?;
+        match conn.query_row(
+            "SELECT content, title, prompt_version, author, languages
+             FROM ZERO_PROMPTS WHERE prompt_name = :1 AND agent_id = :2",
+            &[&name, &self.agent_id],
+        ) {
+            Ok(row) => {
+                let content: String = row.get(0)?;
+                let title: Option<String> = row.get(1)?;
+                let version: String = row.get(2)?;
+                let author: String = row.get(3)?;
+                let languages: String = row.get(4)?;
+                let metadata = PromptMetadata {
+                    title: title.unwrap_or_else(|| name.to_string()),
+                    version,
+                    author,
+                    languages: languages.split(',').map(str::to_string).collect(),
+                };
+                Ok(Some((metadata, content)))
+            }
+            Err(oracle::Error::NoDataFound) => Ok(None),
+            Err(e) => Err(anyhow::anyhow!(
+                "Failed to get prompt with metadata '{name}': {e}"
+            )),
+        }
+    }
+
+    /// Resolve the best-matching variant of `name` for `lang`: an exact
+    /// `"{name}@{lang}"` match first, then the wildcard `"{name}@*"`
+    /// variant, then any stored `"{name}@..."` variant, and finally the
+    /// bare `name` itself for prompts that were never split into
+    /// per-language variants. Returns `None` if nothing matches.
+    ///
+    /// Deliberately does not consult the `languages` column: `ZERO_PROMPTS`
+    /// has one row per `(prompt_name, agent_id)`, so a single row can't hold
+    /// more than one language's content for `name` to select between. The
+    /// `@lang` row-naming convention is the only way this store actually
+    /// represents per-language variants.
+    pub fn get_prompt_for_language(&self, name: &str, lang: &str) -> anyhow::Result<Option<String>> {
+        if let Some(content) =
+            self.get_prompt(&format!("{name}{LANGUAGE_VARIANT_SEP}{lang}"))?
+        {
+            return Ok(Some(content));
+        }
+        if let Some(content) = self.get_prompt(&format!("{name}{LANGUAGE_VARIANT_SEP}*"))? {
+            return Ok(Some(content));
+        }
+
+        let prefix = format!("{name}{LANGUAGE_VARIANT_SEP}");
+        let like_pattern = format!("{prefix}%");
+        let conn = self.pool.acquire()?;
+        let rows = conn.query(
+            "SELECT content FROM ZERO_PROMPTS
+             WHERE agent_id = :1 AND prompt_name LIKE :2
+             ORDER BY prompt_name
+             FETCH FIRST 1 ROW ONLY",
+            &[&self.agent_id, &like_pattern],
+        )?;
+        for row_result in rows {
+            let row = row_result?;
+            return Ok(Some(row.get(0)?));
+        }
+        drop(conn);
+
+        self.get_prompt(name)
+    }
+
+    /// Set a prompt by name (upsert). `content` may carry a leading YAML
+    /// frontmatter block, which is parsed out and stored in its own columns
+    /// -- only the body ends up in `content`. Also appends a row to
+    /// `ZERO_PROMPT_HISTORY` with the next `version_number` for `name`, so
+    /// the previous content is never lost to the `MERGE`'s overwrite --
+    /// see [`Self::list_revisions`] and [`Self::rollback`].
     pub fn set_prompt(&self, name: &str, content: &str) -> anyhow::Result<()> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let (metadata, body) = parse_frontmatter(name, content);
+        let languages = metadata.languages.join(",");
+        let conn = self.pool.acquire()?;
+
+        let current_version = match conn.query_row_as::<i64>(
+            "SELECT version FROM ZERO_PROMPTS WHERE prompt_name = :1 AND agent_id = :2",
+            &[&name, &self.agent_id],
+        ) {
+            Ok(version) => version,
+            Err(oracle::Error::NoDataFound) => 0,
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to read current version of prompt '{name}': {e}"
+                ))
+            }
+        };
+        let next_version = current_version + 1;
+
         conn.execute(
             "MERGE INTO ZERO_PROMPTS p
              USING (SELECT :1 AS prompt_name, :2 AS agent_id FROM DUAL) src
              ON (p.prompt_name = src.prompt_name AND p.agent_id = src.agent_id)
-             WHEN MATCHED THEN UPDATE SET content = :3, updated_at = CURRENT_TIMESTAMP
-             WHEN NOT MATCHED THEN INSERT (prompt_name, agent_id, content)
-                VALUES (:4, :5, :6)",
+             WHEN MATCHED THEN UPDATE SET
+                content = :3, title = :4, prompt_version = :5, author = :6,
+                languages = :7, version = :8, updated_at = CURRENT_TIMESTAMP
+             WHEN NOT MATCHED THEN INSERT
+                (prompt_name, agent_id, content, title, prompt_version, author, languages, version)
+                VALUES (:9, :10, :11, :12, :13, :14, :15, :16)",
             &[
                 &name,
                 &self.agent_id,
-                &content,
+                &body,
+                &metadata.title,
+                &metadata.version,
+                &metadata.author,
+                &languages,
+                &next_version,
                 &name,
                 &self.agent_id,
-                &content,
+                &body,
+                &metadata.title,
+                &metadata.version,
+                &metadata.author,
+                &languages,
+                &next_version,
             ],
         )?;
+
+        conn.execute(
+            "INSERT INTO ZERO_PROMPT_HISTORY (prompt_name, agent_id, content, version_number)
+             VALUES (:1, :2, :3, :4)",
+            &[&name, &self.agent_id, &body, &next_version],
+        )?;
+
+        conn.commit()?;
+        debug!("Prompt set: '{name}' (version {next_version})");
+        Ok(())
+    }
+
+    /// List every revision of `name`, newest first, as `(version_number,
+    /// created_at)` pairs.
+    pub fn list_revisions(&self, name: &str) -> anyhow::Result<Vec<(u32, DateTime<Local>)>> {
+        let conn = self.pool.acquire()?;
+        let rows = conn.query(
+            "SELECT version_number, TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS')
+             FROM ZERO_PROMPT_HISTORY
+             WHERE prompt_name = :1 AND agent_id = :2
+             ORDER BY version_number DESC",
+            &[&name, &self.agent_id],
+        )?;
+        let mut revisions = Vec::new();
+        for row_result in rows {
+            let row = row_result?;
+            let version: i64 = row.get(0)?;
+            let created_at: String = row.get(1)?;
+            if let Some(created_at) = parse_timestamp(&created_at) {
+                revisions.push((version as u32, created_at));
+            }
+        }
+        Ok(revisions)
+    }
+
+    /// Get the content of one past revision of `name`. Returns `None` if
+    /// that `(name, version)` pair has no history row.
+    pub fn get_revision(&self, name: &str, version: u32) -> anyhow::Result<Option<String>> {
+        let conn = self.pool.acquire()?;
+        let version = i64::from(version);
+        match conn.query_row(
+            "SELECT content FROM ZERO_PROMPT_HISTORY
+             WHERE prompt_name = :1 AND agent_id = :2 AND version_number = :3",
+            &[&name, &self.agent_id, &version],
+        ) {
+            Ok(row) => Ok(Some(row.get(0)?)),
+            Err(oracle::Error::NoDataFound) => Ok(None),
+            Err(e) => Err(anyhow::anyhow!(
+                "Failed to get revision {version} of prompt '{name}': {e}"
+            )),
+        }
+    }
+
+    /// Copy revision `version` of `name`'s content back in as the new
+    /// current version. Recorded as its own new revision (rather than
+    /// rewinding `version_number`), so history stays linear -- rolling back
+    /// and then forward again is just another `set_prompt`/`rollback` call.
+    pub fn rollback(&self, name: &str, version: u32) -> anyhow::Result<()> {
+        let conn = self.pool.acquire()?;
+        let version_i64 = i64::from(version);
+        let content: String = match conn.query_row(
+            "SELECT content FROM ZERO_PROMPT_HISTORY
+             WHERE prompt_name = :1 AND agent_id = :2 AND version_number = :3",
+            &[&name, &self.agent_id, &version_i64],
+        ) {
+            Ok(row) => row.get(0)?,
+            Err(oracle::Error::NoDataFound) => {
+                return Err(anyhow::anyhow!("No revision {version} found for prompt '{name}'"));
+            }
+            Err(e) => {
+                return Err(anyhow::anyhow!(
+                    "Failed to read revision {version} of prompt '{name}': {e}"
+                ))
+            }
+        };
+
+        let current_version = conn.query_row_as::<i64>(
+            "SELECT version FROM ZERO_PROMPTS WHERE prompt_name = :1 AND agent_id = :2",
+            &[&name, &self.agent_id],
+        )?;
+        let next_version = current_version + 1;
+
+        conn.execute(
+            "UPDATE ZERO_PROMPTS SET content = :1, version = :2, updated_at = CURRENT_TIMESTAMP
+             WHERE prompt_name = :3 AND agent_id = :4",
+            &[&content, &next_version, &name, &self.agent_id],
+        )?;
+        conn.execute(
+            "INSERT INTO ZERO_PROMPT_HISTORY (prompt_name, agent_id, content, version_number)
+             VALUES (:1, :2, :3, :4)",
+            &[&name, &self.agent_id, &content, &next_version],
+        )?;
         conn.commit()?;
-        debug!("Prompt set: '{name}'");
+        debug!("Prompt '{name}' rolled back to revision {version} (now version {next_version})");
         Ok(())
     }
 
     /// List all prompt names for this agent.
     pub fn list_prompts(&self) -> anyhow::Result<Vec<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         let rows = conn.query(
             "SELECT prompt_name FROM ZERO_PROMPTS WHERE agent_id = :1 ORDER BY prompt_name",
             &[&self.agent_id],
@@ -77,11 +488,113 @@ impl OraclePromptStore {
         Ok(names)
     }
 
+    /// Fuzzy-search prompt names and titles for `query`, returning ranked
+    /// [`PromptMatch`]es (best match per prompt, whichever of name/title
+    /// scored higher) sorted by descending score, ties broken by shorter
+    /// matched text. Prompts where neither field contains `query` as an
+    /// in-order subsequence are omitted.
+    pub fn search_prompts(&self, query: &str) -> anyhow::Result<Vec<PromptMatch>> {
+        let conn = self.pool.acquire()?;
+        let rows = conn.query(
+            "SELECT prompt_name, title FROM ZERO_PROMPTS WHERE agent_id = :1",
+            &[&self.agent_id],
+        )?;
+        let mut candidates = Vec::new();
+        for row_result in rows {
+            let row = row_result?;
+            let name: String = row.get(0)?;
+            let title: Option<String> = row.get(1)?;
+            candidates.push((name, title));
+        }
+        drop(conn);
+
+        let mut matches = Vec::new();
+        for (name, title) in candidates {
+            let title = title.unwrap_or_else(|| name.clone());
+            let name_match = fuzzy_match(query, &name)
+                .map(|(score, indices)| (PromptMatchField::Name, name.clone(), score, indices));
+            let title_match = fuzzy_match(query, &title)
+                .map(|(score, indices)| (PromptMatchField::Title, title.clone(), score, indices));
+
+            let best = match (name_match, title_match) {
+                (Some(n), Some(t)) if t.2 > n.2 => Some(t),
+                (Some(n), _) => Some(n),
+                (None, Some(t)) => Some(t),
+                (None, None) => None,
+            };
+
+            if let Some((field, matched_text, score, matched_indices)) = best {
+                matches.push(PromptMatch {
+                    name,
+                    field,
+                    matched_text,
+                    score,
+                    matched_indices,
+                });
+            }
+        }
+
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.matched_text.len().cmp(&b.matched_text.len()))
+        });
+        Ok(matches)
+    }
+
+    /// Serialize every prompt for this agent (name, metadata, body) into one
+    /// self-describing document: each prompt is a section starting with a
+    /// `<!-- ZEROORACLAW_PROMPT: name -->` header line, followed by its YAML
+    /// frontmatter and body -- the same shape [`Self::set_prompt`] already
+    /// parses via [`parse_frontmatter`], so a bundle round-trips cleanly
+    /// through [`Self::import_bundle`].
+    pub fn export_bundle(&self) -> anyhow::Result<String> {
+        let mut bundle = String::new();
+        for name in self.list_prompts()? {
+            let Some((metadata, body)) = self.get_prompt_with_metadata(&name)? else {
+                continue;
+            };
+            let yaml = serde_yaml::to_string(&metadata).map_err(|e| {
+                anyhow::anyhow!("Failed to serialize metadata for prompt '{name}': {e}")
+            })?;
+            bundle.push_str(BUNDLE_HEADER_PREFIX);
+            bundle.push_str(&name);
+            bundle.push_str(BUNDLE_HEADER_SUFFIX);
+            bundle.push('\n');
+            bundle.push_str("---\n");
+            bundle.push_str(&yaml);
+            bundle.push_str("---\n");
+            bundle.push_str(body.trim());
+            bundle.push_str("\n\n");
+        }
+        Ok(bundle)
+    }
+
+    /// Load prompts from an [`Self::export_bundle`]-format document. Splits
+    /// `bundle` back into per-prompt sections via [`split_bundle_sections`]
+    /// and upserts each one via [`Self::set_prompt`], which re-parses its
+    /// frontmatter. Names that already exist are skipped unless `overwrite`
+    /// is set. Returns the count actually written.
+    pub fn import_bundle(&self, bundle: &str, overwrite: bool) -> anyhow::Result<usize> {
+        let mut written = 0;
+        for (name, content) in split_bundle_sections(bundle) {
+            if !overwrite && self.get_prompt(&name)?.is_some() {
+                continue;
+            }
+            self.set_prompt(&name, content.trim())?;
+            written += 1;
+        }
+        Ok(written)
+    }
+
     /// Seed prompts from workspace `.md` files.
     ///
     /// Looks for `IDENTITY.md`, `SOUL.md`, `USER.md`, `AGENT.md`, and
     /// `AGENTS.md` in `workspace_dir`.  Each non-empty file is upserted as
-    /// a prompt with the base name (e.g. "IDENTITY").
+    /// a prompt with the base name (e.g. "IDENTITY"), with any leading YAML
+    /// frontmatter parsed into metadata rather than staying in the body. A
+    /// file with malformed frontmatter is still seeded, with metadata
+    /// defaults -- it does not abort the rest of the seed run.
     ///
     /// Returns the number of prompts seeded.
     pub fn seed_from_workspace(&self, workspace_dir: &Path) -> anyhow::Result<usize> {
@@ -111,3 +624,59 @@ impl OraclePromptStore {
         Ok(count)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_requires_in_order_subsequence() {
+        assert!(fuzzy_match("rst", "rust").is_some());
+        assert!(fuzzy_match("tsr", "rust").is_none());
+        assert!(fuzzy_match("xyz", "rust").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_scores_consecutive_and_boundary_matches_higher() {
+        let (consecutive_score, _) = fuzzy_match("rus", "rust").unwrap();
+        let (scattered_score, _) = fuzzy_match("rst", "rust").unwrap();
+        assert!(consecutive_score > scattered_score);
+
+        let (boundary_score, _) = fuzzy_match("id", "identity").unwrap();
+        let (mid_word_score, _) = fuzzy_match("id", "xxidxx").unwrap();
+        assert!(boundary_score > mid_word_score);
+    }
+
+    #[test]
+    fn fuzzy_match_returns_matched_indices() {
+        let (_, indices) = fuzzy_match("it", "identity").unwrap();
+        for idx in &indices {
+            assert!(*idx < "identity".chars().count());
+        }
+        assert_eq!(indices.len(), 2);
+    }
+
+    #[test]
+    fn fuzzy_match_rejects_non_subsequence() {
+        assert!(fuzzy_match("identityy", "identity").is_none());
+    }
+
+    #[test]
+    fn split_bundle_sections_recovers_names_and_bodies() {
+        let bundle = "<!-- ZEROORACLAW_PROMPT: IDENTITY -->\n---\ntitle: IDENTITY\n---\nfirst body\n\n<!-- ZEROORACLAW_PROMPT: SOUL -->\n---\ntitle: SOUL\n---\nsecond body\n";
+        let sections = split_bundle_sections(bundle);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].0, "IDENTITY");
+        assert!(sections[0].1.contains("first body"));
+        assert_eq!(sections[1].0, "SOUL");
+        assert!(sections[1].1.contains("second body"));
+    }
+
+    #[test]
+    fn split_bundle_sections_ignores_content_before_first_header() {
+        let bundle = "stray preamble\n<!-- ZEROORACLAW_PROMPT: IDENTITY -->\nbody\n";
+        let sections = split_bundle_sections(bundle);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].0, "IDENTITY");
+    }
+}
@@ -0,0 +1,178 @@
+//! Oracle-backed persistence for [`crate::memory::response_cache::ResponseCache`].
+//!
+//! The in-memory `HashMap` stays the hot path for `get`/`put`; this store
+//! only handles write-through on `put`, rehydration into that map on
+//! startup, and a lazily-flushed batch update of `accessed_at`/`hit_count`
+//! so repeated `get`s never cost a round trip. Mirrors the write-through
+//! shape of [`crate::oracle::embedding::OracleEmbedding`]'s `ZERO_EMBED_CACHE`
+//! cache, gated behind `[memory] response_cache_persist = true`.
+
+use crate::oracle::connection::RetryableConnection;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use tracing::warn;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// One row rehydrated from `ZERO_RESPONSE_CACHE` on startup.
+pub struct PersistedEntry {
+    pub key: String,
+    pub model: String,
+    pub response: String,
+    pub token_count: u32,
+    pub created_at: DateTime<Local>,
+    pub accessed_at: DateTime<Local>,
+    pub hit_count: u64,
+}
+
+/// One pending `accessed_at`/`hit_count` update, applied in a batch by
+/// [`OracleResponseCacheStore::flush_access_updates`].
+pub struct AccessUpdate {
+    pub key: String,
+    pub accessed_at: DateTime<Local>,
+    pub hit_count: u64,
+}
+
+/// Persistence layer for the response cache, scoped per agent.
+pub struct OracleResponseCacheStore {
+    conn: RetryableConnection,
+    agent_id: String,
+}
+
+impl OracleResponseCacheStore {
+    pub fn new(conn: RetryableConnection, agent_id: &str) -> Self {
+        Self {
+            conn,
+            agent_id: agent_id.to_string(),
+        }
+    }
+
+    /// Load every row for this agent younger than `ttl_minutes`. Any failure
+    /// (including the table not existing yet on an un-migrated schema) is
+    /// treated as an empty result -- the cache just starts cold, which is
+    /// always safe since persistence is an optimization on top of the
+    /// in-memory cache, not its source of truth.
+    pub fn rehydrate(&self, ttl_minutes: i64) -> Vec<PersistedEntry> {
+        match self.rehydrate_inner(ttl_minutes) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Response cache rehydration failed, starting cold: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    fn rehydrate_inner(&self, ttl_minutes: i64) -> anyhow::Result<Vec<PersistedEntry>> {
+        let sql = "
+            SELECT cache_key, model_name, response, token_count, hit_count,
+                   TO_CHAR(created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS'),
+                   TO_CHAR(accessed_at, 'YYYY-MM-DD\"T\"HH24:MI:SS')
+            FROM ZERO_RESPONSE_CACHE
+            WHERE agent_id = :1
+              AND created_at > CURRENT_TIMESTAMP - NUMTODSINTERVAL(:2, 'MINUTE')
+        ";
+        self.conn.with_retry(|conn| {
+            let rows = conn.query(sql, &[&self.agent_id, &ttl_minutes])?;
+            let mut entries = Vec::new();
+            for row_result in rows {
+                let row = row_result?;
+                let key: String = row.get(0)?;
+                let model: String = row.get(1)?;
+                let response: String = row.get(2)?;
+                let token_count: i64 = row.get(3)?;
+                let hit_count: i64 = row.get(4)?;
+                let created_at: String = row.get(5)?;
+                let accessed_at: String = row.get(6)?;
+                entries.push((key, model, response, token_count, hit_count, created_at, accessed_at));
+            }
+            Ok(entries)
+        })
+        .map(|rows| {
+            rows.into_iter()
+                .filter_map(|(key, model, response, token_count, hit_count, created_at, accessed_at)| {
+                    let created_at = parse_timestamp(&created_at)?;
+                    let accessed_at = parse_timestamp(&accessed_at)?;
+                    Some(PersistedEntry {
+                        key,
+                        model,
+                        response,
+                        token_count: token_count.max(0) as u32,
+                        created_at,
+                        accessed_at,
+                        hit_count: hit_count.max(0) as u64,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Write-through a new or updated cache entry. Failures (e.g. the table
+    /// not existing yet) are logged and swallowed -- a missed write-through
+    /// only costs durability across a restart, not correctness of the
+    /// in-memory cache it backs.
+    pub fn write_through(&self, key: &str, model: &str, response: &str, token_count: u32) {
+        let sql = "MERGE INTO ZERO_RESPONSE_CACHE t
+             USING (SELECT :1 AS cache_key, :2 AS agent_id FROM DUAL) src
+             ON (t.cache_key = src.cache_key AND t.agent_id = src.agent_id)
+             WHEN MATCHED THEN UPDATE SET
+                 t.model_name = :3, t.response = :4, t.token_count = :5,
+                 t.created_at = CURRENT_TIMESTAMP, t.accessed_at = CURRENT_TIMESTAMP, t.hit_count = 0
+             WHEN NOT MATCHED THEN INSERT (cache_key, agent_id, model_name, response, token_count)
+                VALUES (:6, :7, :8, :9, :10)";
+        let token_count = i64::from(token_count);
+        let result = self.conn.with_retry(|conn| {
+            conn.execute(
+                sql,
+                &[
+                    &key,
+                    &self.agent_id,
+                    &model,
+                    &response,
+                    &token_count,
+                    &key,
+                    &self.agent_id,
+                    &model,
+                    &response,
+                    &token_count,
+                ],
+            )?;
+            conn.commit()
+        });
+        if let Err(e) = result {
+            warn!("Failed to write-through response cache entry '{key}': {e}");
+        }
+    }
+
+    /// Apply a batch of `accessed_at`/`hit_count` updates in a single Oracle
+    /// array-bind round trip, instead of one round trip per `get`. Rows for
+    /// keys that were evicted or never persisted (e.g. a write-through that
+    /// failed) are silently skipped by the `WHEN MATCHED` clause.
+    pub fn flush_access_updates(&self, updates: &[AccessUpdate]) {
+        if updates.is_empty() {
+            return;
+        }
+        let sql = "MERGE INTO ZERO_RESPONSE_CACHE t
+             USING (SELECT :1 AS cache_key, :2 AS agent_id FROM DUAL) src
+             ON (t.cache_key = src.cache_key AND t.agent_id = src.agent_id)
+             WHEN MATCHED THEN UPDATE SET
+                 t.accessed_at = TO_TIMESTAMP(:3, 'YYYY-MM-DD\"T\"HH24:MI:SS'),
+                 t.hit_count = :4";
+        let result = self.conn.with_retry(|conn| {
+            let mut batch = conn.batch(sql, updates.len()).build()?;
+            for update in updates {
+                let accessed_at = update.accessed_at.format(TIMESTAMP_FORMAT).to_string();
+                let hit_count = update.hit_count as i64;
+                batch.append_row(&[&update.key, &self.agent_id, &accessed_at, &hit_count])?;
+            }
+            batch.execute()?;
+            conn.commit()
+        });
+        if let Err(e) = result {
+            warn!("Failed to flush {} response cache access update(s): {e}", updates.len());
+        }
+    }
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Local>> {
+    let naive = NaiveDateTime::parse_from_str(raw, TIMESTAMP_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).single()
+}
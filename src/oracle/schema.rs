@@ -1,17 +1,102 @@
 //! Oracle schema initialization and migration.
 //!
-//! Creates the 8 `ZERO_*` tables, regular indexes, and vector indexes
+//! Creates the `ZERO_*` tables, regular indexes, and vector indexes
 //! required by ZeroOraClaw.  All DDL is idempotent — existing objects
 //! are silently skipped via ORA-00955 / ORA-01408 error handling.
+//!
+//! Schema evolution is driven by [`MIGRATIONS`]: each step is tagged with a
+//! target version and applied, in order, only if `ZERO_META.schema_version`
+//! for the agent is below it. [`init_schema`] runs the full list, so fresh
+//! and existing databases both converge on the latest version; [`pending_migrations`]
+//! reports what *would* run without touching the database.
 
+use crate::oracle::vector::DistanceMetric;
 use oracle::Connection;
 use tracing::{debug, info, warn};
 
+// ── Vector index configuration ──────────────────────────────────
+
+/// Oracle AI Vector Search index organization.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorIndexOrganization {
+    /// IVF-style: `ORGANIZATION NEIGHBOR PARTITIONS`. Disk-based, scales to
+    /// large vector counts; the default.
+    NeighborPartitions,
+    /// HNSW-style: `ORGANIZATION INMEMORY NEIGHBOR GRAPH`. Faster recall at
+    /// the cost of keeping the whole index in memory.
+    Hnsw {
+        neighbors: u32,
+        ef_construction: u32,
+    },
+}
+
+impl Default for VectorIndexOrganization {
+    fn default() -> Self {
+        VectorIndexOrganization::NeighborPartitions
+    }
+}
+
+/// Configures the `CREATE VECTOR INDEX` DDL that migration 1 generates for
+/// `ZERO_MEMORIES.embedding` and `ZERO_DAILY_NOTES.embedding`.
+///
+/// `metric` must agree with whatever `VECTOR_DISTANCE(...)` calls a query
+/// layer issues against these tables (see `oracle::vector::similarity_from_distance`),
+/// since an index built for one metric doesn't accelerate searches using another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VectorIndexConfig {
+    pub organization: VectorIndexOrganization,
+    pub metric: DistanceMetric,
+    pub target_accuracy: u8,
+}
+
+impl Default for VectorIndexConfig {
+    fn default() -> Self {
+        Self {
+            organization: VectorIndexOrganization::default(),
+            metric: DistanceMetric::default(),
+            target_accuracy: 95,
+        }
+    }
+}
+
+impl VectorIndexConfig {
+    /// Generate `CREATE VECTOR INDEX <index_name> ON <table>(embedding) ...`
+    /// DDL for this config.
+    fn create_index_ddl(&self, index_name: &str, table: &str) -> String {
+        let organization_clause = match self.organization {
+            VectorIndexOrganization::NeighborPartitions => {
+                "ORGANIZATION NEIGHBOR PARTITIONS".to_string()
+            }
+            VectorIndexOrganization::Hnsw {
+                neighbors,
+                ef_construction,
+            } => format!(
+                "ORGANIZATION INMEMORY NEIGHBOR GRAPH
+                 PARAMETERS (TYPE HNSW, NEIGHBORS {neighbors}, EFCONSTRUCTION {ef_construction})"
+            ),
+        };
+        format!(
+            "CREATE VECTOR INDEX {index_name} ON {table}(embedding)
+             {organization_clause}
+             DISTANCE {distance}
+             WITH TARGET ACCURACY {accuracy}",
+            distance = self.metric.as_sql(),
+            accuracy = self.target_accuracy,
+        )
+    }
+}
+
 // ── ORA error codes we intentionally ignore ─────────────────────
+/// ORA-00942: table or view does not exist
+const ORA_TABLE_OR_VIEW_NOT_EXIST: i32 = 942;
 /// ORA-00955: name is already used by an existing object
 const ORA_NAME_ALREADY_USED: i32 = 955;
 /// ORA-01408: such column list already indexed
 const ORA_COLUMN_ALREADY_INDEXED: i32 = 1408;
+/// ORA-01430: column being added already exists in table
+const ORA_COLUMN_ALREADY_EXISTS: i32 = 1430;
+/// ORA-29879: domain index (Oracle Text et al.) already exists
+const ORA_DOMAIN_INDEX_ALREADY_EXISTS: i32 = 29879;
 
 // ── Table DDL ───────────────────────────────────────────────────
 
@@ -102,6 +187,136 @@ CREATE TABLE ZERO_PROMPTS (
     CONSTRAINT pk_zero_prompts PRIMARY KEY (prompt_name, agent_id)
 )";
 
+const CREATE_ZERO_EMBED_CACHE: &str = "
+CREATE TABLE ZERO_EMBED_CACHE (
+    cache_key       VARCHAR2(64)    NOT NULL,
+    model_name      VARCHAR2(128)   NOT NULL,
+    dims            NUMBER(10)      NOT NULL,
+    vector          VECTOR          NOT NULL,
+    created_at      TIMESTAMP       DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT pk_zero_embed_cache PRIMARY KEY (cache_key)
+)";
+
+/// Change history for `ZERO_CONFIG`, appended to by
+/// [`crate::oracle::config_store::OracleConfigStore::set`] before each
+/// update so a prior value can be listed (`history`) or restored (`revert`).
+const CREATE_ZERO_CONFIG_HISTORY: &str = "
+CREATE TABLE ZERO_CONFIG_HISTORY (
+    history_id      NUMBER          GENERATED ALWAYS AS IDENTITY,
+    config_key      VARCHAR2(256)   NOT NULL,
+    agent_id        VARCHAR2(128)   NOT NULL,
+    old_value       CLOB,
+    changed_at      TIMESTAMP       DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT pk_zero_config_history PRIMARY KEY (history_id)
+)";
+
+/// Persisted mirror of [`crate::memory::response_cache::ResponseCache`],
+/// written through on every `put` and rehydrated into the in-memory map on
+/// startup so the cache survives restarts. `accessed_at`/`hit_count` are
+/// updated in lazily-flushed batches rather than once per `get`.
+const CREATE_ZERO_RESPONSE_CACHE: &str = "
+CREATE TABLE ZERO_RESPONSE_CACHE (
+    cache_key       VARCHAR2(64)    NOT NULL,
+    agent_id        VARCHAR2(128)   NOT NULL,
+    model_name      VARCHAR2(128)   NOT NULL,
+    response        CLOB            NOT NULL,
+    token_count     NUMBER(10)      NOT NULL,
+    created_at      TIMESTAMP       DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    accessed_at     TIMESTAMP       DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    hit_count       NUMBER(19)      DEFAULT 0 NOT NULL,
+    CONSTRAINT pk_zero_response_cache PRIMARY KEY (cache_key, agent_id)
+)";
+
+/// Session-private scratch table for [`crate::oracle::embedding::OracleEmbedding`]'s
+/// single round-trip batch embedding: texts are batch-inserted here, then
+/// `VECTOR_EMBEDDING` is applied to the whole table in one `SELECT`.
+/// `ON COMMIT DELETE ROWS` clears it automatically once that transaction
+/// commits, so concurrent sessions never see each other's rows.
+const CREATE_ZERO_EMBED_BATCH: &str = "
+CREATE GLOBAL TEMPORARY TABLE ZERO_EMBED_BATCH (
+    row_idx         NUMBER(10)      NOT NULL,
+    text_data       CLOB            NOT NULL,
+    CONSTRAINT pk_zero_embed_batch PRIMARY KEY (row_idx)
+) ON COMMIT DELETE ROWS";
+
+// ── Incremental column additions ────────────────────────────────
+// Kept as idempotent ALTERs (ignoring ORA-01430) rather than a new table
+// so that existing ZERO_MEMORIES rows pick up TTL/GC support in place.
+
+/// Tracks when a memory becomes eligible for expiry. `NULL` means it never
+/// expires (always the case for `Core` category entries).
+const ALTER_ZERO_MEMORIES_EXPIRES_AT: &str =
+    "ALTER TABLE ZERO_MEMORIES ADD (expires_at TIMESTAMP)";
+
+/// Reference count used to extend `expires_at` instead of deleting an entry
+/// that is still being actively re-stored/re-referenced.
+const ALTER_ZERO_MEMORIES_REF_COUNT: &str =
+    "ALTER TABLE ZERO_MEMORIES ADD (ref_count NUMBER(10) DEFAULT 1 NOT NULL)";
+
+/// Oracle Text domain index backing `CONTAINS(content, ...)` keyword search
+/// in `OracleMemory::recall` -- a relevance-ranked alternative to plain
+/// `LIKE` matching that recall falls back to if this index hasn't been
+/// created yet (e.g. a database still on a pre-migration-7 schema).
+const CREATE_ZERO_MEMORIES_CONTENT_TEXT_INDEX: &str =
+    "CREATE INDEX idx_zero_memories_content_ctx ON ZERO_MEMORIES(content) INDEXTYPE IS CTXSYS.CONTEXT";
+
+/// Append-only revision log for `ZERO_MEMORIES`. `OracleMemory::store`
+/// writes one row per call instead of letting its `MERGE` overwrite
+/// `content` destructively: the previously open row (`valid_to IS NULL`)
+/// is closed with `valid_to = CURRENT_TIMESTAMP` and a new row is opened.
+/// `recall_as_of`/`get_as_of` reconstruct memory state at a past instant by
+/// finding the row where `at` falls in `[valid_from, valid_to)`, and
+/// `history` lists every revision for a key newest-first.
+const CREATE_ZERO_MEMORY_HISTORY: &str = "
+CREATE TABLE ZERO_MEMORY_HISTORY (
+    history_id      NUMBER          GENERATED ALWAYS AS IDENTITY,
+    memory_id       VARCHAR2(64)    NOT NULL,
+    agent_id        VARCHAR2(128)   NOT NULL,
+    key             VARCHAR2(512)   NOT NULL,
+    content         CLOB            NOT NULL,
+    category        VARCHAR2(64)    NOT NULL,
+    session_id      VARCHAR2(128),
+    valid_from      TIMESTAMP       DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    valid_to        TIMESTAMP,
+    CONSTRAINT pk_zero_memory_history PRIMARY KEY (history_id)
+)";
+
+const CREATE_ZERO_MEMORY_HISTORY_KEY_INDEX: &str =
+    "CREATE INDEX idx_zero_memory_history_key ON ZERO_MEMORY_HISTORY(agent_id, key)";
+
+/// Frontmatter metadata columns for `ZERO_PROMPTS`, added alongside the
+/// existing `version` integer column (which tracks the current revision
+/// number against `ZERO_PROMPT_HISTORY`, see migration 10) -- `prompt_version`
+/// here is the distinct, free-form version *string* a prompt's own YAML
+/// frontmatter declares (e.g. `"1.0"`), parsed by
+/// [`crate::oracle::prompt::OraclePromptStore`].
+const ALTER_ZERO_PROMPTS_METADATA: &str = "
+ALTER TABLE ZERO_PROMPTS ADD (
+    title           VARCHAR2(512),
+    prompt_version  VARCHAR2(50)    DEFAULT '1.0' NOT NULL,
+    author          VARCHAR2(256)   DEFAULT 'No Author' NOT NULL,
+    languages       VARCHAR2(1024)  DEFAULT '*' NOT NULL
+)";
+
+/// Append-only revision log for `ZERO_PROMPTS`, mirroring
+/// `ZERO_MEMORY_HISTORY`'s shape but keyed by an auto-incrementing
+/// `version_number` per `(prompt_name, agent_id)` rather than a validity
+/// range, since prompt rollback restores a specific numbered revision
+/// instead of reconstructing state as-of a point in time.
+const CREATE_ZERO_PROMPT_HISTORY: &str = "
+CREATE TABLE ZERO_PROMPT_HISTORY (
+    history_id      NUMBER          GENERATED ALWAYS AS IDENTITY,
+    prompt_name     VARCHAR2(256)   NOT NULL,
+    agent_id        VARCHAR2(128)   NOT NULL,
+    content         CLOB            NOT NULL,
+    version_number  NUMBER(10)      NOT NULL,
+    created_at      TIMESTAMP       DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    CONSTRAINT pk_zero_prompt_history PRIMARY KEY (history_id)
+)";
+
+const CREATE_ZERO_PROMPT_HISTORY_NAME_INDEX: &str =
+    "CREATE INDEX idx_zero_prompt_history_name ON ZERO_PROMPT_HISTORY(agent_id, prompt_name)";
+
 // ── Regular index DDL ───────────────────────────────────────────
 
 const INDEXES: &[&str] = &[
@@ -118,19 +333,6 @@ const INDEXES: &[&str] = &[
     "CREATE INDEX idx_zero_prompts_agent ON ZERO_PROMPTS(agent_id)",
 ];
 
-// ── Vector index DDL ────────────────────────────────────────────
-
-const VECTOR_INDEXES: &[&str] = &[
-    "CREATE VECTOR INDEX vidx_zero_memories_emb ON ZERO_MEMORIES(embedding)
-     ORGANIZATION NEIGHBOR PARTITIONS
-     DISTANCE COSINE
-     WITH TARGET ACCURACY 95",
-    "CREATE VECTOR INDEX vidx_zero_daily_notes_emb ON ZERO_DAILY_NOTES(embedding)
-     ORGANIZATION NEIGHBOR PARTITIONS
-     DISTANCE COSINE
-     WITH TARGET ACCURACY 95",
-];
-
 // ── Helpers ─────────────────────────────────────────────────────
 
 /// Execute DDL, silently ignoring "already exists" errors.
@@ -152,32 +354,106 @@ fn exec_ddl_idempotent(conn: &Connection, sql: &str, ignore_codes: &[i32]) -> an
     }
 }
 
-/// Seed a ZERO_META row for this agent (MERGE = upsert).
-fn seed_meta(conn: &Connection, agent_id: &str) -> anyhow::Result<()> {
+/// Upsert `ZERO_META.schema_version` for this agent (MERGE = upsert).
+fn set_schema_version(conn: &Connection, agent_id: &str, version: i64) -> anyhow::Result<()> {
     let sql = "
         MERGE INTO ZERO_META m
         USING (SELECT :1 AS agent_id FROM DUAL) src
         ON (m.agent_id = src.agent_id)
         WHEN NOT MATCHED THEN
             INSERT (agent_id, schema_version, created_at, updated_at)
-            VALUES (src.agent_id, 1, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
+            VALUES (src.agent_id, :2, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)
         WHEN MATCHED THEN
-            UPDATE SET m.updated_at = CURRENT_TIMESTAMP
+            UPDATE SET m.schema_version = :2, m.updated_at = CURRENT_TIMESTAMP
     ";
-    conn.execute(sql, &[&agent_id])?;
+    conn.execute(sql, &[&agent_id, &version])?;
     Ok(())
 }
 
-// ── Public API ──────────────────────────────────────────────────
+/// Current `schema_version` for `agent_id`, or `0` if `ZERO_META` doesn't
+/// have a row yet (fresh database) or doesn't exist yet (pre-migration-1
+/// database).
+fn current_schema_version(conn: &Connection, agent_id: &str) -> anyhow::Result<i64> {
+    match conn.query_row_as::<i64>(
+        "SELECT schema_version FROM ZERO_META WHERE agent_id = :1",
+        &[&agent_id],
+    ) {
+        Ok(version) => Ok(version),
+        Err(oracle::Error::NoDataFound) => Ok(0),
+        Err(ref e) => match e.db_error() {
+            Some(db_err) if db_err.code() == ORA_TABLE_OR_VIEW_NOT_EXIST => Ok(0),
+            _ => Err(anyhow::anyhow!("Failed to read schema_version: {e}")),
+        },
+    }
+}
 
-/// Initialise the full ZeroOraClaw schema idempotently.
-///
-/// The caller must hold the `Mutex<Connection>` lock and pass the
-/// inner `&Connection`.  This function commits on success.
-pub fn init_schema(conn: &Connection, agent_id: &str) -> anyhow::Result<()> {
-    info!("Initialising Oracle schema for agent '{agent_id}'...");
+// ── Migration steps ─────────────────────────────────────────────
+
+/// One schema migration: a target version and the idempotent DDL/DML that
+/// brings a database from the previous version up to it.
+struct Migration {
+    version: i64,
+    description: &'static str,
+    apply: fn(&Connection, &VectorIndexConfig) -> anyhow::Result<()>,
+}
+
+/// Ordered migration steps, oldest first. Add new steps to the end with the
+/// next version number — never renumber or reorder existing entries, since
+/// `schema_version` values are already persisted in the field.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Create ZERO_* tables, regular indexes, and vector indexes",
+        apply: apply_migration_1,
+    },
+    Migration {
+        version: 2,
+        description: "Add expires_at/ref_count columns to ZERO_MEMORIES for TTL/GC",
+        apply: apply_migration_2,
+    },
+    Migration {
+        version: 3,
+        description: "Create ZERO_EMBED_CACHE for content-addressed embedding reuse",
+        apply: apply_migration_3,
+    },
+    Migration {
+        version: 4,
+        description: "Create ZERO_EMBED_BATCH scratch table for single round-trip batch embedding",
+        apply: apply_migration_4,
+    },
+    Migration {
+        version: 5,
+        description: "Create ZERO_CONFIG_HISTORY for config change history and revert",
+        apply: apply_migration_5,
+    },
+    Migration {
+        version: 6,
+        description: "Create ZERO_RESPONSE_CACHE for persisted response cache write-through",
+        apply: apply_migration_6,
+    },
+    Migration {
+        version: 7,
+        description: "Create Oracle Text index on ZERO_MEMORIES.content for CONTAINS keyword search",
+        apply: apply_migration_7,
+    },
+    Migration {
+        version: 8,
+        description: "Create ZERO_MEMORY_HISTORY for versioned/as-of memory recall",
+        apply: apply_migration_8,
+    },
+    Migration {
+        version: 9,
+        description: "Add title/prompt_version/author/languages metadata columns to ZERO_PROMPTS",
+        apply: apply_migration_9,
+    },
+    Migration {
+        version: 10,
+        description: "Create ZERO_PROMPT_HISTORY for prompt revision history and rollback",
+        apply: apply_migration_10,
+    },
+];
 
-    // 1. Create tables (ignore ORA-00955 "name already used")
+fn apply_migration_1(conn: &Connection, vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
     let table_stmts = [
         ("ZERO_META", CREATE_ZERO_META),
         ("ZERO_MEMORIES", CREATE_ZERO_MEMORIES),
@@ -194,13 +470,15 @@ pub fn init_schema(conn: &Connection, agent_id: &str) -> anyhow::Result<()> {
         exec_ddl_idempotent(conn, ddl, &[ORA_NAME_ALREADY_USED])?;
     }
 
-    // 2. Create regular indexes (ignore ORA-00955 / ORA-01408)
     for idx_ddl in INDEXES {
         exec_ddl_idempotent(conn, idx_ddl, &[ORA_NAME_ALREADY_USED, ORA_COLUMN_ALREADY_INDEXED])?;
     }
 
-    // 3. Create vector indexes (ignore ORA-00955 / ORA-01408)
-    for vidx_ddl in VECTOR_INDEXES {
+    let vector_indexes = [
+        vector_config.create_index_ddl("vidx_zero_memories_emb", "ZERO_MEMORIES"),
+        vector_config.create_index_ddl("vidx_zero_daily_notes_emb", "ZERO_DAILY_NOTES"),
+    ];
+    for vidx_ddl in &vector_indexes {
         exec_ddl_idempotent(
             conn,
             vidx_ddl,
@@ -208,15 +486,143 @@ pub fn init_schema(conn: &Connection, agent_id: &str) -> anyhow::Result<()> {
         )?;
     }
 
-    // 4. Seed meta row for this agent
-    seed_meta(conn, agent_id)?;
+    Ok(())
+}
+
+fn apply_migration_2(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(
+        conn,
+        ALTER_ZERO_MEMORIES_EXPIRES_AT,
+        &[ORA_COLUMN_ALREADY_EXISTS],
+    )?;
+    exec_ddl_idempotent(
+        conn,
+        ALTER_ZERO_MEMORIES_REF_COUNT,
+        &[ORA_COLUMN_ALREADY_EXISTS],
+    )?;
+    Ok(())
+}
+
+fn apply_migration_3(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(conn, CREATE_ZERO_EMBED_CACHE, &[ORA_NAME_ALREADY_USED])?;
+    Ok(())
+}
+
+fn apply_migration_4(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(conn, CREATE_ZERO_EMBED_BATCH, &[ORA_NAME_ALREADY_USED])?;
+    Ok(())
+}
+
+fn apply_migration_5(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(conn, CREATE_ZERO_CONFIG_HISTORY, &[ORA_NAME_ALREADY_USED])?;
+    Ok(())
+}
+
+fn apply_migration_6(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(conn, CREATE_ZERO_RESPONSE_CACHE, &[ORA_NAME_ALREADY_USED])?;
+    Ok(())
+}
+
+fn apply_migration_7(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(
+        conn,
+        CREATE_ZERO_MEMORIES_CONTENT_TEXT_INDEX,
+        &[ORA_NAME_ALREADY_USED, ORA_DOMAIN_INDEX_ALREADY_EXISTS],
+    )?;
+    Ok(())
+}
+
+fn apply_migration_8(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(conn, CREATE_ZERO_MEMORY_HISTORY, &[ORA_NAME_ALREADY_USED])?;
+    exec_ddl_idempotent(
+        conn,
+        CREATE_ZERO_MEMORY_HISTORY_KEY_INDEX,
+        &[ORA_NAME_ALREADY_USED, ORA_COLUMN_ALREADY_INDEXED],
+    )?;
+    Ok(())
+}
 
-    // 5. Commit the transaction
-    conn.commit()?;
-    info!("Oracle schema ready (agent '{agent_id}')");
+fn apply_migration_9(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(
+        conn,
+        ALTER_ZERO_PROMPTS_METADATA,
+        &[ORA_COLUMN_ALREADY_EXISTS],
+    )?;
     Ok(())
 }
 
+fn apply_migration_10(conn: &Connection, _vector_config: &VectorIndexConfig) -> anyhow::Result<()> {
+    exec_ddl_idempotent(conn, CREATE_ZERO_PROMPT_HISTORY, &[ORA_NAME_ALREADY_USED])?;
+    exec_ddl_idempotent(
+        conn,
+        CREATE_ZERO_PROMPT_HISTORY_NAME_INDEX,
+        &[ORA_NAME_ALREADY_USED, ORA_COLUMN_ALREADY_INDEXED],
+    )?;
+    Ok(())
+}
+
+// ── Public API ──────────────────────────────────────────────────
+
+/// Initialise the full ZeroOraClaw schema idempotently.
+///
+/// The caller passes a connection checked out of the pool (a
+/// `PooledConnection` derefs to `&Connection`).  Runs [`run_migrations`] so fresh and existing
+/// databases both converge on the latest schema version, with vector
+/// indexes generated from `vector_config`.
+pub fn init_schema(
+    conn: &Connection,
+    agent_id: &str,
+    vector_config: &VectorIndexConfig,
+) -> anyhow::Result<()> {
+    info!("Initialising Oracle schema for agent '{agent_id}'...");
+    let applied = run_migrations(conn, agent_id, vector_config)?;
+    if applied.is_empty() {
+        debug!("Schema already at latest version for agent '{agent_id}'");
+    } else {
+        info!("Applied schema migrations {applied:?} for agent '{agent_id}'");
+    }
+    Ok(())
+}
+
+/// Apply every migration whose version is greater than the agent's current
+/// `schema_version`, in order, bumping `schema_version` after each one.
+/// Commits once, after the last migration applied. Returns the versions
+/// that were applied (empty if already up to date).
+pub fn run_migrations(
+    conn: &Connection,
+    agent_id: &str,
+    vector_config: &VectorIndexConfig,
+) -> anyhow::Result<Vec<i64>> {
+    let current = current_schema_version(conn, agent_id)?;
+    let mut applied = Vec::new();
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        debug!(
+            "Applying schema migration {} ({})",
+            migration.version, migration.description
+        );
+        (migration.apply)(conn, vector_config)?;
+        set_schema_version(conn, agent_id, migration.version)?;
+        applied.push(migration.version);
+    }
+
+    if !applied.is_empty() {
+        conn.commit()?;
+    }
+    Ok(applied)
+}
+
+/// Report which migrations `run_migrations` would apply for `agent_id`,
+/// without executing or committing anything.
+pub fn pending_migrations(conn: &Connection, agent_id: &str) -> anyhow::Result<Vec<(i64, &'static str)>> {
+    let current = current_schema_version(conn, agent_id)?;
+    Ok(MIGRATIONS
+        .iter()
+        .filter(|m| m.version > current)
+        .map(|m| (m.version, m.description))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,17 +654,125 @@ mod tests {
 
     #[test]
     fn vector_index_count_is_correct() {
-        assert_eq!(VECTOR_INDEXES.len(), 2);
+        let vector_indexes = [
+            VectorIndexConfig::default().create_index_ddl("vidx_zero_memories_emb", "ZERO_MEMORIES"),
+            VectorIndexConfig::default().create_index_ddl("vidx_zero_daily_notes_emb", "ZERO_DAILY_NOTES"),
+        ];
+        assert_eq!(vector_indexes.len(), 2);
     }
 
     #[test]
-    fn vector_indexes_use_cosine_distance() {
-        for vidx in VECTOR_INDEXES {
-            assert!(vidx.contains("COSINE"), "Vector index missing COSINE: {vidx}");
-            assert!(
-                vidx.contains("TARGET ACCURACY 95"),
-                "Vector index missing TARGET ACCURACY: {vidx}"
-            );
+    fn ttl_columns_are_idempotent_alters() {
+        assert!(ALTER_ZERO_MEMORIES_EXPIRES_AT.contains("expires_at"));
+        assert!(ALTER_ZERO_MEMORIES_REF_COUNT.contains("ref_count"));
+    }
+
+    #[test]
+    fn migrations_start_at_one_and_are_strictly_increasing() {
+        assert_eq!(MIGRATIONS[0].version, 1);
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[1].version > pair[0].version, "migrations must be strictly increasing");
         }
     }
+
+    #[test]
+    fn migration_2_covers_the_ttl_columns() {
+        let migration_2 = MIGRATIONS.iter().find(|m| m.version == 2).unwrap();
+        assert!(migration_2.description.contains("expires_at"));
+        assert!(migration_2.description.contains("ref_count"));
+    }
+
+    #[test]
+    fn migration_3_creates_embed_cache_table() {
+        assert!(CREATE_ZERO_EMBED_CACHE.contains("pk_zero_embed_cache"));
+        assert!(CREATE_ZERO_EMBED_CACHE.contains("cache_key"));
+        let migration_3 = MIGRATIONS.iter().find(|m| m.version == 3).unwrap();
+        assert!(migration_3.description.contains("ZERO_EMBED_CACHE"));
+    }
+
+    #[test]
+    fn migration_4_creates_embed_batch_scratch_table() {
+        assert!(CREATE_ZERO_EMBED_BATCH.contains("GLOBAL TEMPORARY TABLE"));
+        assert!(CREATE_ZERO_EMBED_BATCH.contains("ON COMMIT DELETE ROWS"));
+        let migration_4 = MIGRATIONS.iter().find(|m| m.version == 4).unwrap();
+        assert!(migration_4.description.contains("ZERO_EMBED_BATCH"));
+    }
+
+    #[test]
+    fn migration_5_creates_config_history_table() {
+        assert!(CREATE_ZERO_CONFIG_HISTORY.contains("pk_zero_config_history"));
+        assert!(CREATE_ZERO_CONFIG_HISTORY.contains("old_value"));
+        let migration_5 = MIGRATIONS.iter().find(|m| m.version == 5).unwrap();
+        assert!(migration_5.description.contains("ZERO_CONFIG_HISTORY"));
+    }
+
+    #[test]
+    fn migration_6_creates_response_cache_table() {
+        assert!(CREATE_ZERO_RESPONSE_CACHE.contains("pk_zero_response_cache"));
+        assert!(CREATE_ZERO_RESPONSE_CACHE.contains("hit_count"));
+        let migration_6 = MIGRATIONS.iter().find(|m| m.version == 6).unwrap();
+        assert!(migration_6.description.contains("ZERO_RESPONSE_CACHE"));
+    }
+
+    #[test]
+    fn migration_7_creates_text_index_on_content() {
+        assert!(CREATE_ZERO_MEMORIES_CONTENT_TEXT_INDEX.contains("CTXSYS.CONTEXT"));
+        assert!(CREATE_ZERO_MEMORIES_CONTENT_TEXT_INDEX.contains("ZERO_MEMORIES(content)"));
+        let migration_7 = MIGRATIONS.iter().find(|m| m.version == 7).unwrap();
+        assert!(migration_7.description.contains("CONTAINS"));
+    }
+
+    #[test]
+    fn migration_8_creates_memory_history_table() {
+        assert!(CREATE_ZERO_MEMORY_HISTORY.contains("pk_zero_memory_history"));
+        assert!(CREATE_ZERO_MEMORY_HISTORY.contains("valid_from"));
+        assert!(CREATE_ZERO_MEMORY_HISTORY.contains("valid_to"));
+        assert!(CREATE_ZERO_MEMORY_HISTORY_KEY_INDEX.contains("ZERO_MEMORY_HISTORY"));
+        let migration_8 = MIGRATIONS.iter().find(|m| m.version == 8).unwrap();
+        assert!(migration_8.description.contains("ZERO_MEMORY_HISTORY"));
+    }
+
+    #[test]
+    fn migration_9_adds_prompt_metadata_columns() {
+        assert!(ALTER_ZERO_PROMPTS_METADATA.contains("title"));
+        assert!(ALTER_ZERO_PROMPTS_METADATA.contains("prompt_version"));
+        assert!(ALTER_ZERO_PROMPTS_METADATA.contains("author"));
+        assert!(ALTER_ZERO_PROMPTS_METADATA.contains("languages"));
+        let migration_9 = MIGRATIONS.iter().find(|m| m.version == 9).unwrap();
+        assert!(migration_9.description.contains("ZERO_PROMPTS"));
+    }
+
+    #[test]
+    fn migration_10_creates_prompt_history_table() {
+        assert!(CREATE_ZERO_PROMPT_HISTORY.contains("pk_zero_prompt_history"));
+        assert!(CREATE_ZERO_PROMPT_HISTORY.contains("version_number"));
+        assert!(CREATE_ZERO_PROMPT_HISTORY_NAME_INDEX.contains("ZERO_PROMPT_HISTORY"));
+        let migration_10 = MIGRATIONS.iter().find(|m| m.version == 10).unwrap();
+        assert!(migration_10.description.contains("ZERO_PROMPT_HISTORY"));
+    }
+
+    #[test]
+    fn vector_indexes_use_cosine_distance() {
+        let vidx = VectorIndexConfig::default().create_index_ddl("vidx_zero_memories_emb", "ZERO_MEMORIES");
+        assert!(vidx.contains("COSINE"), "Vector index missing COSINE: {vidx}");
+        assert!(
+            vidx.contains("TARGET ACCURACY 95"),
+            "Vector index missing TARGET ACCURACY: {vidx}"
+        );
+    }
+
+    #[test]
+    fn hnsw_organization_uses_neighbor_graph_parameters() {
+        let config = VectorIndexConfig {
+            organization: VectorIndexOrganization::Hnsw {
+                neighbors: 32,
+                ef_construction: 128,
+            },
+            ..VectorIndexConfig::default()
+        };
+        let vidx = config.create_index_ddl("vidx_zero_memories_emb", "ZERO_MEMORIES");
+        assert!(vidx.contains("ORGANIZATION INMEMORY NEIGHBOR GRAPH"));
+        assert!(vidx.contains("NEIGHBORS 32"));
+        assert!(vidx.contains("EFCONSTRUCTION 128"));
+    }
 }
@@ -5,51 +5,67 @@
 //! synchronous so callers should wrap calls in `spawn_blocking` if
 //! needed from async contexts.
 
-use oracle::Connection;
-use std::sync::{Arc, Mutex};
+use crate::oracle::connection::{ConnectionPool, PooledConnection};
 use tracing::{debug, info};
 
 /// Persistent chat session store backed by Oracle Database.
 pub struct OracleSessionStore {
-    conn: Arc<Mutex<Connection>>,
+    pool: ConnectionPool,
     agent_id: String,
 }
 
 impl OracleSessionStore {
-    pub fn new(conn: Arc<Mutex<Connection>>, agent_id: &str) -> Self {
+    pub fn new(pool: ConnectionPool, agent_id: &str) -> Self {
         Self {
-            conn,
+            pool,
             agent_id: agent_id.to_string(),
         }
     }
 
+    /// Run `f` as a single unit of work: every call on the passed
+    /// [`SessionTransaction`] shares one underlying Oracle transaction,
+    /// committed exactly once if `f` returns `Ok`, rolled back as a whole
+    /// if it returns `Err`.
+    ///
+    /// ```ignore
+    /// store.transaction(|tx| {
+    ///     tx.save_messages("session-1", &messages_json)?;
+    ///     tx.append_transcript("session-1", "user", "hello")?;
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn transaction<F, T>(&self, f: F) -> anyhow::Result<T>
+    where
+        F: FnOnce(&SessionTransaction<'_>) -> anyhow::Result<T>,
+    {
+        let conn = self.pool.acquire()?;
+        let tx = SessionTransaction {
+            conn: &conn,
+            agent_id: &self.agent_id,
+        };
+
+        match f(&tx) {
+            Ok(value) => {
+                conn.commit()?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(rollback_err) = conn.rollback() {
+                    tracing::warn!("Rollback also failed: {rollback_err}");
+                }
+                Err(e)
+            }
+        }
+    }
+
     /// Save messages JSON for a session key (upsert).
     pub fn save_messages(&self, session_key: &str, messages_json: &str) -> anyhow::Result<()> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        conn.execute(
-            "MERGE INTO ZERO_SESSIONS s
-             USING (SELECT :1 AS session_key, :2 AS agent_id FROM DUAL) src
-             ON (s.session_key = src.session_key AND s.agent_id = src.agent_id)
-             WHEN MATCHED THEN UPDATE SET messages = :3, updated_at = CURRENT_TIMESTAMP
-             WHEN NOT MATCHED THEN INSERT (session_key, agent_id, messages)
-                VALUES (:4, :5, :6)",
-            &[
-                &session_key,
-                &self.agent_id,
-                &messages_json,
-                &session_key,
-                &self.agent_id,
-                &messages_json,
-            ],
-        )?;
-        conn.commit()?;
-        debug!("Saved messages for session '{session_key}'");
-        Ok(())
+        self.transaction(|tx| tx.save_messages(session_key, messages_json))
     }
 
     /// Load messages JSON for a session key. Returns `None` if not found.
     pub fn load_messages(&self, session_key: &str) -> anyhow::Result<Option<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         match conn.query_row(
             "SELECT messages FROM ZERO_SESSIONS WHERE session_key = :1 AND agent_id = :2",
             &[&session_key, &self.agent_id],
@@ -70,20 +86,12 @@ impl OracleSessionStore {
         role: &str,
         content: &str,
     ) -> anyhow::Result<()> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        conn.execute(
-            "INSERT INTO ZERO_TRANSCRIPTS (agent_id, role, content, session_id)
-             VALUES (:1, :2, :3, :4)",
-            &[&self.agent_id, &role, &content, &session_key],
-        )?;
-        conn.commit()?;
-        debug!("Appended transcript ({role}) for session '{session_key}'");
-        Ok(())
+        self.transaction(|tx| tx.append_transcript(session_key, role, content))
     }
 
     /// List all session keys for this agent, most recently updated first.
     pub fn list_sessions(&self) -> anyhow::Result<Vec<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         let rows = conn.query(
             "SELECT session_key FROM ZERO_SESSIONS WHERE agent_id = :1 ORDER BY updated_at DESC",
             &[&self.agent_id],
@@ -98,20 +106,73 @@ impl OracleSessionStore {
 
     /// Delete a session and its transcripts.
     pub fn delete_session(&self, session_key: &str) -> anyhow::Result<bool> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
-        // Delete transcripts first (foreign-key-like relationship via session_id)
-        conn.execute(
+        self.transaction(|tx| tx.delete_session(session_key))
+    }
+}
+
+/// A logical unit of work against `ZERO_SESSIONS`/`ZERO_TRANSCRIPTS`.
+///
+/// Borrowed for the lifetime of a single [`OracleSessionStore::transaction`]
+/// call; none of its methods commit or roll back on their own -- the
+/// enclosing `transaction` call does that once, for every statement issued
+/// through this handle.
+pub struct SessionTransaction<'a> {
+    conn: &'a PooledConnection,
+    agent_id: &'a str,
+}
+
+impl SessionTransaction<'_> {
+    /// Save messages JSON for a session key (upsert), without committing.
+    pub fn save_messages(&self, session_key: &str, messages_json: &str) -> anyhow::Result<()> {
+        self.conn.execute(
+            "MERGE INTO ZERO_SESSIONS s
+             USING (SELECT :1 AS session_key, :2 AS agent_id FROM DUAL) src
+             ON (s.session_key = src.session_key AND s.agent_id = src.agent_id)
+             WHEN MATCHED THEN UPDATE SET messages = :3, updated_at = CURRENT_TIMESTAMP
+             WHEN NOT MATCHED THEN INSERT (session_key, agent_id, messages)
+                VALUES (:4, :5, :6)",
+            &[
+                &session_key,
+                &self.agent_id,
+                &messages_json,
+                &session_key,
+                &self.agent_id,
+                &messages_json,
+            ],
+        )?;
+        debug!("Saved messages for session '{session_key}' (uncommitted)");
+        Ok(())
+    }
+
+    /// Append a transcript entry to `ZERO_TRANSCRIPTS`, without committing.
+    pub fn append_transcript(
+        &self,
+        session_key: &str,
+        role: &str,
+        content: &str,
+    ) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO ZERO_TRANSCRIPTS (agent_id, role, content, session_id)
+             VALUES (:1, :2, :3, :4)",
+            &[&self.agent_id, &role, &content, &session_key],
+        )?;
+        debug!("Appended transcript ({role}) for session '{session_key}' (uncommitted)");
+        Ok(())
+    }
+
+    /// Delete a session and its transcripts, without committing.
+    pub fn delete_session(&self, session_key: &str) -> anyhow::Result<bool> {
+        self.conn.execute(
             "DELETE FROM ZERO_TRANSCRIPTS WHERE session_id = :1 AND agent_id = :2",
             &[&session_key, &self.agent_id],
         )?;
-        let stmt = conn.execute(
+        let stmt = self.conn.execute(
             "DELETE FROM ZERO_SESSIONS WHERE session_key = :1 AND agent_id = :2",
             &[&session_key, &self.agent_id],
         )?;
         let deleted = stmt.row_count()? > 0;
-        conn.commit()?;
         if deleted {
-            info!("Deleted session '{session_key}'");
+            info!("Deleted session '{session_key}' (uncommitted)");
         }
         Ok(deleted)
     }
@@ -4,27 +4,34 @@
 //! The `oracle` crate is synchronous so callers should wrap calls in
 //! `spawn_blocking` if needed from async contexts.
 
+use crate::oracle::connection::ConnectionPool;
 use oracle::Connection;
-use std::sync::{Arc, Mutex};
 use tracing::debug;
 
+/// One row for [`OracleStateStore::set_many`].
+#[derive(Debug, Clone)]
+pub struct StatePut {
+    pub key: String,
+    pub value: String,
+}
+
 /// Persistent key-value state store backed by Oracle Database.
 pub struct OracleStateStore {
-    conn: Arc<Mutex<Connection>>,
+    pool: ConnectionPool,
     agent_id: String,
 }
 
 impl OracleStateStore {
-    pub fn new(conn: Arc<Mutex<Connection>>, agent_id: &str) -> Self {
+    pub fn new(pool: ConnectionPool, agent_id: &str) -> Self {
         Self {
-            conn,
+            pool,
             agent_id: agent_id.to_string(),
         }
     }
 
     /// Set a key-value pair (upsert).
     pub fn set(&self, key: &str, value: &str) -> anyhow::Result<()> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         conn.execute(
             "MERGE INTO ZERO_STATE s
              USING (SELECT :1 AS state_key, :2 AS agent_id FROM DUAL) src
@@ -41,7 +48,7 @@ impl OracleStateStore {
 
     /// Get a value by key. Returns `None` if not found.
     pub fn get(&self, key: &str) -> anyhow::Result<Option<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         match conn.query_row(
             "SELECT value FROM ZERO_STATE WHERE state_key = :1 AND agent_id = :2",
             &[&key, &self.agent_id],
@@ -57,7 +64,7 @@ impl OracleStateStore {
 
     /// Delete a key. Returns `true` if a row was deleted.
     pub fn delete(&self, key: &str) -> anyhow::Result<bool> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         let stmt = conn.execute(
             "DELETE FROM ZERO_STATE WHERE state_key = :1 AND agent_id = :2",
             &[&key, &self.agent_id],
@@ -70,9 +77,67 @@ impl OracleStateStore {
         Ok(deleted)
     }
 
+    /// Bulk-upsert `entries` using Oracle array binding, chunked into
+    /// `batch_size`-row round trips.
+    ///
+    /// Returns one `Result` per input entry, in the same order. A chunk that
+    /// executes cleanly reports every row in it as `Ok`; if the batch as a
+    /// whole fails, that chunk is retried row-by-row so a single bad entry
+    /// doesn't sink its neighbours.
+    pub fn set_many(
+        &self,
+        entries: &[StatePut],
+        batch_size: usize,
+    ) -> anyhow::Result<Vec<anyhow::Result<()>>> {
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let batch_size = batch_size.max(1);
+        let conn = self.pool.acquire()?;
+
+        let mut results = Vec::with_capacity(entries.len());
+        for chunk in entries.chunks(batch_size) {
+            match Self::set_batch(&conn, &self.agent_id, chunk) {
+                Ok(()) => results.extend(chunk.iter().map(|_| Ok(()))),
+                Err(e) => {
+                    debug!(
+                        "Batch of {} state rows failed ({e}), retrying row-by-row",
+                        chunk.len()
+                    );
+                    for put in chunk {
+                        results.push(Self::set_batch(
+                            &conn,
+                            &self.agent_id,
+                            std::slice::from_ref(put),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    /// Execute one chunk of `set_many` as a single Oracle array-bind batch.
+    fn set_batch(conn: &Connection, agent_id: &str, chunk: &[StatePut]) -> anyhow::Result<()> {
+        let sql = "MERGE INTO ZERO_STATE s
+             USING (SELECT :1 AS state_key, :2 AS agent_id FROM DUAL) src
+             ON (s.state_key = src.state_key AND s.agent_id = src.agent_id)
+             WHEN MATCHED THEN UPDATE SET value = :3, updated_at = CURRENT_TIMESTAMP
+             WHEN NOT MATCHED THEN INSERT (state_key, agent_id, value)
+                VALUES (:4, :5, :6)";
+
+        let mut batch = conn.batch(sql, chunk.len()).build()?;
+        for put in chunk {
+            batch.append_row(&[&put.key, agent_id, &put.value, &put.key, agent_id, &put.value])?;
+        }
+        batch.execute()?;
+        conn.commit()?;
+        Ok(())
+    }
+
     /// List all state keys for this agent.
     pub fn list_keys(&self) -> anyhow::Result<Vec<String>> {
-        let conn = self.conn.lock().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let conn = self.pool.acquire()?;
         let rows = conn.query(
             "SELECT state_key FROM ZERO_STATE WHERE agent_id = :1 ORDER BY state_key",
             &[&self.agent_id],
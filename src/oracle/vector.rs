@@ -5,6 +5,102 @@
 
 use std::fmt::Write;
 
+/// Oracle AI Vector Search distance metric, as passed to `VECTOR_DISTANCE(...)`
+/// and `CREATE VECTOR INDEX ... DISTANCE ...`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+    Cosine,
+    Euclidean,
+    Dot,
+    Manhattan,
+}
+
+impl Default for DistanceMetric {
+    fn default() -> Self {
+        DistanceMetric::Cosine
+    }
+}
+
+impl DistanceMetric {
+    /// The literal Oracle expects after `DISTANCE` / as the third
+    /// `VECTOR_DISTANCE` argument.
+    pub fn as_sql(self) -> &'static str {
+        match self {
+            DistanceMetric::Cosine => "COSINE",
+            DistanceMetric::Euclidean => "EUCLIDEAN",
+            DistanceMetric::Dot => "DOT",
+            DistanceMetric::Manhattan => "MANHATTAN",
+        }
+    }
+}
+
+/// On-disk element format of an Oracle `VECTOR` column, i.e. the second
+/// argument of `VECTOR(dims, <format>)`. Controls how [`decode_vector_bytes`]
+/// interprets the raw bytes Oracle returns when a `VECTOR` is selected
+/// without `TO_CHAR`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorFormat {
+    /// 4 bytes per element, IEEE 754 single precision. What every table in
+    /// this schema stores (`VECTOR(384, FLOAT32)`).
+    Float32,
+    /// 8 bytes per element, IEEE 754 double precision.
+    Float64,
+    /// 1 byte per element, a signed integer quantized representation.
+    /// `scale` is the model-specific dequantization factor: `element as f32
+    /// * scale` recovers an approximate `f32`.
+    Int8 { scale: f32 },
+    /// 1 bit per element, packed 8 to a byte, MSB first. Each bit decodes to
+    /// `+1.0`/`-1.0`, the usual binary-quantization convention.
+    Binary,
+}
+
+impl Default for VectorFormat {
+    fn default() -> Self {
+        VectorFormat::Float32
+    }
+}
+
+/// Decode raw `VECTOR` column bytes into `Vec<f32>` per `format`, avoiding
+/// the precision loss and overhead of parsing `TO_CHAR(vector)` text.
+pub fn decode_vector_bytes(bytes: &[u8], format: VectorFormat) -> anyhow::Result<Vec<f32>> {
+    match format {
+        VectorFormat::Float32 => {
+            if bytes.len() % 4 != 0 {
+                anyhow::bail!(
+                    "FLOAT32 vector byte length {} is not a multiple of 4",
+                    bytes.len()
+                );
+            }
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .collect())
+        }
+        VectorFormat::Float64 => {
+            if bytes.len() % 8 != 0 {
+                anyhow::bail!(
+                    "FLOAT64 vector byte length {} is not a multiple of 8",
+                    bytes.len()
+                );
+            }
+            Ok(bytes
+                .chunks_exact(8)
+                .map(|c| f64::from_le_bytes(c.try_into().expect("chunk is 8 bytes")) as f32)
+                .collect())
+        }
+        VectorFormat::Int8 { scale } => Ok(bytes.iter().map(|&b| (b as i8) as f32 * scale).collect()),
+        VectorFormat::Binary => {
+            let mut out = Vec::with_capacity(bytes.len() * 8);
+            for &byte in bytes {
+                for bit in (0..8).rev() {
+                    out.push(if (byte >> bit) & 1 == 1 { 1.0 } else { -1.0 });
+                }
+            }
+            Ok(out)
+        }
+    }
+}
+
 /// Convert a `&[f32]` slice into Oracle's `TO_VECTOR()` compatible string.
 ///
 /// Output format: `[0.1, 0.2, -0.3, ...]`
@@ -24,12 +120,20 @@ pub fn vec_to_oracle_string(v: &[f32]) -> String {
     buf
 }
 
-/// Convert a cosine distance (0.0 = identical, 2.0 = opposite) to a similarity
-/// score in the range `[0.0, 1.0]`.
+/// Convert a raw `VECTOR_DISTANCE` value into a similarity score in the
+/// range `[0.0, 1.0]` (higher = more similar), using the conversion
+/// appropriate for `metric` so ranking stays correct across index types.
 ///
-/// Formula: `max(1.0 - distance, 0.0)`
-pub fn similarity_from_distance(distance: f64) -> f64 {
-    (1.0 - distance).max(0.0)
+/// * `Cosine` distance is in `[0, 2]`; similarity is `1.0 - distance`, clamped.
+/// * `Euclidean`/`Manhattan` distance is unbounded `>= 0`; similarity is `1.0 / (1.0 + distance)`.
+/// * `Dot` "distance" is the negated dot product for normalized vectors
+///   (more negative = more similar), so similarity is `-distance`, clamped.
+pub fn similarity_from_distance(distance: f64, metric: DistanceMetric) -> f64 {
+    match metric {
+        DistanceMetric::Cosine => (1.0 - distance).max(0.0),
+        DistanceMetric::Euclidean | DistanceMetric::Manhattan => 1.0 / (1.0 + distance.max(0.0)),
+        DistanceMetric::Dot => (-distance).clamp(0.0, 1.0),
+    }
 }
 
 #[cfg(test)]
@@ -62,23 +166,83 @@ mod tests {
 
     #[test]
     fn similarity_identical() {
-        assert!((similarity_from_distance(0.0) - 1.0).abs() < f64::EPSILON);
+        assert!((similarity_from_distance(0.0, DistanceMetric::Cosine) - 1.0).abs() < f64::EPSILON);
     }
 
     #[test]
     fn similarity_half() {
-        assert!((similarity_from_distance(0.5) - 0.5).abs() < f64::EPSILON);
+        assert!((similarity_from_distance(0.5, DistanceMetric::Cosine) - 0.5).abs() < f64::EPSILON);
     }
 
     #[test]
     fn similarity_opposite() {
-        assert!((similarity_from_distance(1.0) - 0.0).abs() < f64::EPSILON);
+        assert!((similarity_from_distance(1.0, DistanceMetric::Cosine) - 0.0).abs() < f64::EPSILON);
     }
 
     #[test]
     fn similarity_clamps_negative() {
         // distance > 1.0 should still return 0.0 (clamped)
-        assert!((similarity_from_distance(1.5) - 0.0).abs() < f64::EPSILON);
-        assert!((similarity_from_distance(2.0) - 0.0).abs() < f64::EPSILON);
+        assert!((similarity_from_distance(1.5, DistanceMetric::Cosine) - 0.0).abs() < f64::EPSILON);
+        assert!((similarity_from_distance(2.0, DistanceMetric::Cosine) - 0.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn distance_metric_sql_literals() {
+        assert_eq!(DistanceMetric::Cosine.as_sql(), "COSINE");
+        assert_eq!(DistanceMetric::Euclidean.as_sql(), "EUCLIDEAN");
+        assert_eq!(DistanceMetric::Dot.as_sql(), "DOT");
+        assert_eq!(DistanceMetric::Manhattan.as_sql(), "MANHATTAN");
+    }
+
+    #[test]
+    fn similarity_euclidean_decreases_with_distance() {
+        let near = similarity_from_distance(0.1, DistanceMetric::Euclidean);
+        let far = similarity_from_distance(10.0, DistanceMetric::Euclidean);
+        assert!(near > far);
+        assert!(near <= 1.0 && far >= 0.0);
+    }
+
+    #[test]
+    fn similarity_dot_uses_negated_distance() {
+        // A `VECTOR_DISTANCE(..., DOT)` of -0.9 means a dot product of 0.9.
+        assert!((similarity_from_distance(-0.9, DistanceMetric::Dot) - 0.9).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn decode_float32_round_trips() {
+        let values = [0.1f32, -2.5, 3.0, 0.0];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let decoded = decode_vector_bytes(&bytes, VectorFormat::Float32).unwrap();
+        assert_eq!(decoded, values.to_vec());
+    }
+
+    #[test]
+    fn decode_float32_rejects_misaligned_length() {
+        assert!(decode_vector_bytes(&[0u8, 1, 2], VectorFormat::Float32).is_err());
+    }
+
+    #[test]
+    fn decode_float64_downcasts_to_f32() {
+        let values = [1.5f64, -9.25];
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let decoded = decode_vector_bytes(&bytes, VectorFormat::Float64).unwrap();
+        assert_eq!(decoded, vec![1.5f32, -9.25f32]);
+    }
+
+    #[test]
+    fn decode_int8_dequantizes_with_scale() {
+        let bytes = [127u8, 0, 255]; // 127, 0, -1 as i8
+        let decoded = decode_vector_bytes(&bytes, VectorFormat::Int8 { scale: 0.5 }).unwrap();
+        assert_eq!(decoded, vec![63.5, 0.0, -0.5]);
+    }
+
+    #[test]
+    fn decode_binary_unpacks_bits_msb_first() {
+        // 0b1010_0000 -> +1,-1,+1,-1,-1,-1,-1,-1
+        let decoded = decode_vector_bytes(&[0b1010_0000], VectorFormat::Binary).unwrap();
+        assert_eq!(
+            decoded,
+            vec![1.0, -1.0, 1.0, -1.0, -1.0, -1.0, -1.0, -1.0]
+        );
     }
 }
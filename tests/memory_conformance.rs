@@ -0,0 +1,162 @@
+//! Integration coverage for `zerooraclaw::memory::run_memory_conformance`.
+//!
+//! Runs the conformance suite against a local in-memory `Memory` mock
+//! unconditionally, and against a real Oracle-backed store when
+//! `ZEROORACLAW_ORACLE_INTEGRATION_TESTS=1` is set alongside the usual
+//! `ZEROORACLAW_ORACLE_*` connection env vars, since that path needs a live
+//! database.
+//!
+//! Uses a local in-memory backend since `InMemoryTestBackend` from the
+//! library is `#[cfg(test)]`-gated (only available for unit tests, not
+//! integration tests) -- see `tests/memory_restart.rs`.
+
+use async_trait::async_trait;
+use zerooraclaw::memory::run_memory_conformance;
+use zerooraclaw::memory::traits::{Memory, MemoryCategory, MemoryEntry};
+
+struct TestMemory {
+    entries: parking_lot::Mutex<Vec<MemoryEntry>>,
+}
+
+impl TestMemory {
+    fn new() -> Self {
+        Self {
+            entries: parking_lot::Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Memory for TestMemory {
+    fn name(&self) -> &str {
+        "test_memory"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let mut entries = self.entries.lock();
+        if let Some(existing) = entries.iter_mut().find(|e| e.key == key) {
+            existing.content = content.to_string();
+            existing.category = category;
+            existing.timestamp = chrono::Utc::now().to_rfc3339();
+            existing.session_id = session_id.map(str::to_string);
+        } else {
+            entries.push(MemoryEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                key: key.to_string(),
+                content: content.to_string(),
+                category,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                session_id: session_id.map(str::to_string),
+                score: None,
+            });
+        }
+        Ok(())
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let entries = self.entries.lock();
+        let query_lower = query.to_ascii_lowercase();
+        let mut results: Vec<_> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                let content_match = e.content.to_ascii_lowercase().contains(&query_lower)
+                    || e.key.to_ascii_lowercase().contains(&query_lower);
+                let session_match =
+                    session_id.map_or(true, |sid| e.session_id.as_deref() == Some(sid));
+                content_match && session_match
+            })
+            .map(|(idx, e)| {
+                let mut e = e.clone();
+                // Fake but monotonic relevance score: earlier insertions
+                // rank higher, so `run_memory_conformance`'s monotonicity
+                // check has something meaningful to verify.
+                e.score = Some(1.0 / (idx as f64 + 1.0));
+                e
+            })
+            .collect();
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let entries = self.entries.lock();
+        Ok(entries.iter().find(|e| e.key == key).cloned())
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let entries = self.entries.lock();
+        let results: Vec<_> = entries
+            .iter()
+            .filter(|e| {
+                let cat_match = category.map_or(true, |c| e.category == *c);
+                let session_match =
+                    session_id.map_or(true, |sid| e.session_id.as_deref() == Some(sid));
+                cat_match && session_match
+            })
+            .cloned()
+            .collect();
+        Ok(results)
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        let mut entries = self.entries.lock();
+        let len_before = entries.len();
+        entries.retain(|e| e.key != key);
+        Ok(entries.len() < len_before)
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        Ok(self.entries.lock().len())
+    }
+
+    async fn health_check(&self) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn conformance_suite_passes_against_local_in_memory_backend() {
+    let mem = TestMemory::new();
+    let report = run_memory_conformance(&mem).await;
+    assert!(report.is_ok(), "conformance failures: {:?}", report.failed);
+}
+
+/// Same suite, against a real Oracle-backed `OracleMemory` -- gated behind
+/// `ZEROORACLAW_ORACLE_INTEGRATION_TESTS=1` since it needs a live database
+/// reachable via the usual `ZEROORACLAW_ORACLE_*` env vars.
+#[tokio::test]
+async fn conformance_suite_passes_against_oracle() {
+    if std::env::var("ZEROORACLAW_ORACLE_INTEGRATION_TESTS").as_deref() != Ok("1") {
+        eprintln!(
+            "skipping conformance_suite_passes_against_oracle: set \
+             ZEROORACLAW_ORACLE_INTEGRATION_TESTS=1 (plus ZEROORACLAW_ORACLE_* \
+             connection vars) to run this against a live database"
+        );
+        return;
+    }
+
+    let mem = zerooraclaw::memory::create_oracle_memory_for_migration()
+        .expect("create_oracle_memory_for_migration should succeed against a configured Oracle instance");
+
+    let report = run_memory_conformance(&mem).await;
+    assert!(report.is_ok(), "conformance failures: {:?}", report.failed);
+}